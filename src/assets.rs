@@ -8,6 +8,9 @@ use rkit::{
 };
 
 use crate::consts::*;
+use crate::game::{BuildKind, ResourceKind};
+use crate::rivals::{RivalProfiles, parse_rivals};
+use crate::save::SaveFile;
 use crate::screens::AppScreen;
 
 const ASSETS_DIR: &str = "./assets";
@@ -38,13 +41,15 @@ impl AssetLoader {
 
         let list = AssetList::new(&[
             &data_dir("kenney_pixel-webfont.ttf"),
+            &data_dir("rivals.ini"),
             &img_dir("spritesheet.png"),
         ])
         .with_extension_parser("png", move |id, data| {
             parse_sprite(id, data, &nearest_sampler)
         })
         .with_extension_parser("ogg", parse_ogg)
-        .with_extension_parser("ttf", parse_font);
+        .with_extension_parser("ttf", parse_font)
+        .with_extension_parser("ini", parse_ini);
 
         Ok(Self(list))
     }
@@ -65,6 +70,12 @@ fn parse_ogg(_id: &str, data: &[u8]) -> Result<Sound, String> {
     create_sound(data)
 }
 
+/// Data files are kept raw, each one is read by its own module
+fn parse_ini(_id: &str, data: &[u8]) -> Result<SaveFile, String> {
+    let text = std::str::from_utf8(data).map_err(|e| e.to_string())?;
+    Ok(SaveFile::parse(text))
+}
+
 fn img_dir(d: &str) -> String {
     format!("{}/images/{}", ASSETS_DIR, d)
 }
@@ -102,6 +113,7 @@ fn update_assets_loader_system(mut loader: ResMut<AssetLoader>, mut cmds: Comman
 #[derive(Resource)]
 pub struct Assets {
     pub font: Font,
+    pub rivals: RivalProfiles,
 
     pub empty_square: Sprite,
     pub dotted_square: Sprite,
@@ -122,11 +134,14 @@ pub struct Assets {
     pub shop: Sprite,
     pub mine: Sprite,
     pub land: Sprite,
+    pub warehouse: Sprite,
+    pub depot: Sprite,
 }
 
 impl Assets {
     fn new(list: &AssetMap) -> Result<Self, String> {
         let font = list.get(&data_dir("kenney_pixel-webfont.ttf"))?;
+        let rivals = parse_rivals(&list.get(&data_dir("rivals.ini"))?)?;
 
         let tile_size = Vec2::splat(TILE_SIZE);
         let spritesheet = list.get::<Sprite>(&img_dir("spritesheet.png"))?;
@@ -157,9 +172,13 @@ impl Assets {
         let shop = spritesheet.clone_with_frame(Rect::new(vec2(TILE_SIZE * 4.0, row), tile_size));
         let mine = spritesheet.clone_with_frame(Rect::new(vec2(TILE_SIZE * 5.0, row), tile_size));
         let land = spritesheet.clone_with_frame(Rect::new(vec2(TILE_SIZE * 6.0, row), tile_size));
+        let warehouse =
+            spritesheet.clone_with_frame(Rect::new(vec2(TILE_SIZE * 7.0, row), tile_size));
+        let depot = spritesheet.clone_with_frame(Rect::new(vec2(TILE_SIZE * 8.0, row), tile_size));
 
         Ok(Self {
             font,
+            rivals,
             empty_square,
             dotted_square,
             white_square,
@@ -179,6 +198,34 @@ impl Assets {
             shop,
             mine,
             land,
+            warehouse,
+            depot,
         })
     }
+
+    pub fn resource(&self, kind: ResourceKind) -> &Sprite {
+        match kind {
+            ResourceKind::Copper => &self.copper,
+            ResourceKind::Iron => &self.iron,
+            ResourceKind::Silver => &self.silver,
+            ResourceKind::Gold => &self.gold,
+            ResourceKind::Woord => &self.wood,
+            ResourceKind::Food => &self.food,
+            ResourceKind::People => &self.people,
+            ResourceKind::Ring => &self.ring,
+        }
+    }
+
+    pub fn building(&self, kind: BuildKind) -> &Sprite {
+        match kind {
+            BuildKind::Farm => &self.farm,
+            BuildKind::House => &self.house,
+            BuildKind::Forest => &self.forest,
+            BuildKind::Factory => &self.factory,
+            BuildKind::Shop => &self.shop,
+            BuildKind::Mine => &self.mine,
+            BuildKind::Warehouse => &self.warehouse,
+            BuildKind::Depot => &self.depot,
+        }
+    }
 }
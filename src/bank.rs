@@ -0,0 +1,423 @@
+use rkit::prelude::*;
+
+use crate::{
+    consts::*,
+    economy::{Money, SimClock, SimSet},
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::create_text_btn,
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+};
+
+pub fn bank_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_bank_system)
+        .add_screen_systems(screen, OnUpdate, SimSet::Settle.on_tick(loans_system))
+        .add_screen_systems(screen, OnUpdate, update_bank_panel_system);
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LoanOffer {
+    pub amount: f32,
+    pub periods: u32,
+    /// Interest for each payment period before the credit penalty
+    pub base_rate: f32,
+}
+
+const LOAN_OFFERS: [LoanOffer; 3] = [
+    LoanOffer {
+        amount: 200.0,
+        periods: 4,
+        base_rate: 0.02,
+    },
+    LoanOffer {
+        amount: 1000.0,
+        periods: 8,
+        base_rate: 0.03,
+    },
+    LoanOffer {
+        amount: 5000.0,
+        periods: 12,
+        base_rate: 0.045,
+    },
+];
+
+#[derive(Clone, Debug)]
+pub struct Loan {
+    pub principal: f32,
+    pub balance: f32,
+    /// Interest compounded each tick
+    pub tick_rate: f32,
+    pub payment: f32,
+    pub periods_left: u32,
+    pub next_due: u64,
+    /// Interest accrued since the last payment
+    pub accrued: f32,
+}
+
+#[derive(Resource)]
+pub struct Bank {
+    pub loans: Vec<Loan>,
+    /// Credit score from 0 to 100, lower scores get higher rates
+    pub credit: f32,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self {
+            loans: vec![],
+            credit: CREDIT_MAX,
+        }
+    }
+}
+
+impl Bank {
+    /// Interest for each payment period of the offer with the current credit score
+    pub fn period_rate_for(&self, offer: &LoanOffer) -> f32 {
+        let penalty = (1.0 - self.credit / CREDIT_MAX) * CREDIT_RATE_PENALTY;
+        offer.base_rate + penalty
+    }
+
+    pub fn take_loan(&mut self, offer: &LoanOffer, tick: u64) -> f32 {
+        let period_rate = self.period_rate_for(offer);
+        self.loans.push(Loan {
+            principal: offer.amount,
+            balance: offer.amount,
+            tick_rate: tick_rate(period_rate, LOAN_PERIOD_TICKS),
+            payment: annuity_payment(offer.amount, period_rate, offer.periods),
+            periods_left: offer.periods,
+            next_due: tick + LOAN_PERIOD_TICKS as u64,
+            accrued: 0.0,
+        });
+        offer.amount
+    }
+
+    pub fn debt(&self) -> f32 {
+        self.loans.iter().map(|loan| loan.balance).sum()
+    }
+}
+
+// - interest math
+
+/// Balance after compounding the rate during the number of ticks
+pub fn compound(balance: f32, rate: f32, ticks: u32) -> f32 {
+    balance * (1.0 + rate).powi(ticks as i32)
+}
+
+/// Rate compounded each tick that gives the same interest as the period rate
+pub fn tick_rate(period_rate: f32, ticks: u32) -> f32 {
+    (1.0 + period_rate).powf(1.0 / ticks as f32) - 1.0
+}
+
+/// Fixed payment that repays the principal and its interest in the number of periods
+pub fn annuity_payment(principal: f32, period_rate: f32, periods: u32) -> f32 {
+    if periods == 0 {
+        return principal;
+    }
+
+    if period_rate <= 0.0 {
+        return principal / periods as f32;
+    }
+
+    principal * period_rate / (1.0 - (1.0 + period_rate).powi(-(periods as i32)))
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct BankPanel;
+
+#[derive(Component, Clone, Copy)]
+struct BankCreditText;
+
+#[derive(Component, Clone, Copy)]
+struct LoanOfferText(usize);
+
+#[derive(Component, Clone, Copy)]
+struct LoanOfferBtn(usize);
+
+#[derive(Component, Clone, Copy)]
+struct LoansList;
+
+#[derive(Component, Clone, Copy)]
+struct LoanRow;
+
+pub fn create_bank_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Bank",
+        48.0,
+        UIOnClick::run(toggle_bank_panel_system),
+    )
+}
+
+fn toggle_bank_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<BankPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Bank", BankPanel);
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (BankCreditText, panel_text("", PICO8_WHITE)),
+        );
+
+        LOAN_OFFERS.iter().enumerate().for_each(|(idx, _)| {
+            let row = spawn_panel_node(cmds, panel, content, panel_row());
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (LoanOfferText(idx), panel_text("", PICO8_LIGHT_GRAY)),
+            );
+            let btn = create_text_btn(
+                cmds,
+                UIGameLayout,
+                "Take",
+                32.0,
+                (
+                    PanelNode(panel),
+                    LoanOfferBtn(idx),
+                    UIOnClick::run(take_loan_system),
+                ),
+            );
+            cmds.add_ui_child(UIGameLayout, row, btn);
+        });
+
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (
+                LoansList,
+                UIContainer::default(),
+                UIStyle::default().flex_col().gap_y(1.0),
+            ),
+        );
+    });
+}
+
+fn take_loan_system(
+    In(entity): In<Entity>,
+    btns: Query<&LoanOfferBtn>,
+    mut bank: ResMut<Bank>,
+    mut money: ResMut<Money>,
+    clock: Res<SimClock>,
+    mut notifications: ResMut<Notifications>,
+    mut ledger: ResMut<Ledger>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    let offer = &LOAN_OFFERS[btn.0];
+    if bank.loans.len() >= MAX_LOANS {
+        notifications.push("The bank refuses more loans", PICO8_ORANGE, None);
+        return;
+    }
+
+    let amount = bank.take_loan(offer, clock.tick);
+    money.0 += amount;
+    ledger.money(LedgerCategory::Loans, amount);
+}
+
+fn update_bank_panel_system(
+    mut cmds: Commands,
+    mut credit_text: Query<&mut UIText, With<BankCreditText>>,
+    mut offer_texts: Query<(&mut UIText, &LoanOfferText), Without<BankCreditText>>,
+    list: Query<(Entity, &PanelNode), With<LoansList>>,
+    new_list: Query<(), Added<LoansList>>,
+    rows: Query<Entity, With<LoanRow>>,
+    bank: Res<Bank>,
+    clock: Res<SimClock>,
+) {
+    let Ok((list, panel)) = list.get_single() else {
+        return;
+    };
+
+    credit_text.iter_mut().for_each(|mut text| {
+        text.text = format!("Credit score: {:.0}/{CREDIT_MAX:.0}", bank.credit);
+    });
+
+    offer_texts.iter_mut().for_each(|(mut text, offer)| {
+        let loan = &LOAN_OFFERS[offer.0];
+        let rate = bank.period_rate_for(loan) * 100.0;
+        text.text = format!(
+            "${:.0} in {} payments at {rate:.1}%",
+            loan.amount, loan.periods
+        );
+    });
+
+    // loans are updated each tick
+    if !bank.is_changed() && new_list.is_empty() {
+        return;
+    }
+
+    let layout = UIGameLayout;
+    rows.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+    bank.loans.iter().for_each(|loan| {
+        let secs = loan.next_due.saturating_sub(clock.tick) as f32 * SIM_TICK_TIME;
+        let txt = format!(
+            "${:.0} loan: owe ${:.0}, pay ${:.0} in {secs:.0}s",
+            loan.principal, loan.balance, loan.payment
+        );
+        spawn_panel_node(
+            &mut cmds,
+            panel.0,
+            list,
+            (LoanRow, panel_text(txt, PICO8_PEACH)),
+        );
+    });
+}
+
+// - systems
+fn init_bank_system(mut cmds: Commands) {
+    cmds.insert_resource(Bank::default());
+}
+
+fn loans_system(
+    mut bank: ResMut<Bank>,
+    mut money: ResMut<Money>,
+    clock: Res<SimClock>,
+    mut notifications: ResMut<Notifications>,
+    mut ledger: ResMut<Ledger>,
+) {
+    let tick = clock.tick;
+    let mut credit_delta = 0.0;
+    bank.loans.iter_mut().for_each(|loan| {
+        let balance = compound(loan.balance, loan.tick_rate, 1);
+        loan.accrued += balance - loan.balance;
+        loan.balance = balance;
+        if tick < loan.next_due {
+            return;
+        }
+
+        loan.next_due += LOAN_PERIOD_TICKS as u64;
+        let payment = if loan.periods_left <= 1 {
+            loan.balance
+        } else {
+            loan.payment.min(loan.balance)
+        };
+
+        if money.0 < payment {
+            // missed payments make the loan and the future ones more expensive
+            loan.balance += payment * LATE_PAYMENT_FEE;
+            credit_delta -= CREDIT_MISSED_PENALTY;
+            notifications.push(
+                format!("Missed a loan payment of ${payment:.0}"),
+                PICO8_RED,
+                None,
+            );
+            return;
+        }
+
+        // split the payment to know how much of it was interest
+        let interest = payment.min(loan.accrued);
+        loan.accrued -= interest;
+        ledger.money(LedgerCategory::Interest, -interest);
+        ledger.money(LedgerCategory::Loans, interest - payment);
+
+        money.0 -= payment;
+        loan.balance -= payment;
+        loan.periods_left = loan.periods_left.saturating_sub(1);
+        credit_delta += CREDIT_PAYMENT_BONUS;
+    });
+
+    bank.loans.retain(|loan| loan.balance > 0.01);
+    bank.credit = (bank.credit + credit_delta).clamp(0.0, CREDIT_MAX);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() <= tolerance, "{a} is not close to {b}");
+    }
+
+    #[test]
+    fn compound_matches_the_closed_form() {
+        let (balance, rate) = (1000.0, 0.01);
+        [0, 1, 10, 30, 120].into_iter().for_each(|ticks| {
+            let stepped = (0..ticks).fold(balance, |b, _| compound(b, rate, 1));
+            let closed = balance * (1.0 + rate).powf(ticks as f32);
+            assert_near(compound(balance, rate, ticks), closed, closed * 1e-4);
+            assert_near(stepped, closed, closed * 1e-4);
+        });
+    }
+
+    #[test]
+    fn tick_rate_compounds_back_to_the_period_rate() {
+        [0.02, 0.03, 0.045, 0.125]
+            .into_iter()
+            .for_each(|period_rate| {
+                let rate = tick_rate(period_rate, LOAN_PERIOD_TICKS);
+                assert!(rate > 0.0 && rate < period_rate);
+                let grown = compound(1.0, rate, LOAN_PERIOD_TICKS);
+                assert_near(grown, 1.0 + period_rate, 1e-5);
+            });
+    }
+
+    #[test]
+    fn annuity_payment_pays_off_the_principal() {
+        LOAN_OFFERS.iter().for_each(|offer| {
+            let payment = annuity_payment(offer.amount, offer.base_rate, offer.periods);
+            let left = (0..offer.periods).fold(offer.amount, |balance, _| {
+                balance * (1.0 + offer.base_rate) - payment
+            });
+            assert_near(left, 0.0, offer.amount * 1e-4);
+            assert!(payment * offer.periods as f32 > offer.amount);
+        });
+    }
+
+    #[test]
+    fn loan_is_repaid_by_its_schedule() {
+        let mut bank = Bank::default();
+        let offer = &LOAN_OFFERS[1];
+        bank.take_loan(offer, 0);
+        let loan = &mut bank.loans[0];
+        (1..=offer.periods * LOAN_PERIOD_TICKS).for_each(|tick| {
+            loan.balance = compound(loan.balance, loan.tick_rate, 1);
+            if tick as u64 == loan.next_due {
+                loan.next_due += LOAN_PERIOD_TICKS as u64;
+                loan.balance -= loan.payment;
+            }
+        });
+        assert_near(loan.balance, 0.0, offer.amount * 1e-3);
+    }
+
+    #[test]
+    fn zero_rate_has_no_interest() {
+        assert_eq!(compound(500.0, 0.0, 90), 500.0);
+        assert_eq!(tick_rate(0.0, LOAN_PERIOD_TICKS), 0.0);
+        assert_eq!(annuity_payment(1200.0, 0.0, 12), 100.0);
+        assert_eq!(annuity_payment(1200.0, 0.05, 0), 1200.0);
+    }
+
+    #[test]
+    fn bad_credit_raises_the_rate() {
+        let offer = &LOAN_OFFERS[0];
+        let mut bank = Bank::default();
+        assert_eq!(bank.period_rate_for(offer), offer.base_rate);
+        bank.credit = 0.0;
+        assert_near(
+            bank.period_rate_for(offer),
+            offer.base_rate + CREDIT_RATE_PENALTY,
+            1e-6,
+        );
+    }
+}
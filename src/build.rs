@@ -0,0 +1,500 @@
+use rkit::{math::UVec2, prelude::*};
+
+use crate::{
+    camera::{CamShake, focus_game_cam},
+    components::Pos,
+    consts::*,
+    economy::{Money, SimClock},
+    game::{BuildKind, Building, Land},
+    hazards::Safety,
+    inflation::Inflation,
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    screens::AppScreen,
+    ui::btns::UIImgButton,
+    undo::{UndoEntry, UndoStack},
+};
+
+pub fn build_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_build_system)
+        .add_screen_systems(screen, OnUpdate, update_tool_btns_system);
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    BuyLand,
+    Build(BuildKind),
+    Dig,
+    Abandon,
+    Secure(Safety),
+    Demolish,
+    Move,
+}
+
+/// Building picked by the move tool, the next click moves it
+#[derive(Resource, Default)]
+pub struct MovePick(Option<Entity>);
+
+/// What happened when an action was reverted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reverted {
+    No,
+    Yes,
+    /// The building came back as a new entity
+    Respawned(Entity),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BuildAction {
+    BuyLand {
+        land: Entity,
+    },
+    Build {
+        land: Entity,
+        pos: UVec2,
+        kind: BuildKind,
+    },
+    Upgrade {
+        building: Entity,
+    },
+    Dig {
+        land: Entity,
+        tile: UVec2,
+    },
+    Abandon {
+        land: Entity,
+        tile: UVec2,
+    },
+    Secure {
+        land: Entity,
+        tile: UVec2,
+        safety: Safety,
+    },
+    Demolish {
+        building: Entity,
+        kind: BuildKind,
+        /// State of the building to bring it back on undo
+        snapshot: Building,
+    },
+    Move {
+        building: Entity,
+        from: UVec2,
+        to: UVec2,
+    },
+}
+
+impl BuildAction {
+    /// Returns the building affected by the action
+    pub fn apply(self, world: &mut World) -> Option<Entity> {
+        match self {
+            BuildAction::BuyLand { land } => {
+                if let Some(mut land) = world.get_mut::<Land>(land) {
+                    land.owned = true;
+                }
+
+                if let Some(pos) = world.get::<Pos>(land) {
+                    focus_game_cam(pos.0, None)(world);
+                }
+                None
+            }
+            BuildAction::Build { land, pos, kind } => {
+                Some(world.spawn((Building::new(land, pos), kind)).id())
+            }
+            BuildAction::Upgrade { building } => {
+                let mut b = world.get_mut::<Building>(building)?;
+                b.level += 1;
+                b.produced = false;
+                Some(building)
+            }
+            BuildAction::Dig { land, tile } => {
+                let mut land = world.get_mut::<Land>(land)?;
+                land.excavation_mut(tile)?.progress = Some(0.0);
+                None
+            }
+            BuildAction::Abandon { land, tile } => {
+                // the mine on the tile is removed along with the tile
+                let buildings = world.get::<Land>(land)?.buildings.clone();
+                let mine = buildings.into_iter().find(|e| {
+                    world.get::<Building>(*e).is_some_and(|b| b.pos == tile)
+                        && world.get::<BuildKind>(*e) == Some(&BuildKind::Mine)
+                });
+
+                {
+                    let mut land_c = world.get_mut::<Land>(land)?;
+                    land_c.excavation_mut(tile)?.abandoned = true;
+                    if let Some(mine) = mine {
+                        land_c.remove(&mine);
+                    }
+                }
+
+                if let Some(mine) = mine {
+                    world.despawn(mine);
+                }
+                None
+            }
+            BuildAction::Secure { land, tile, safety } => {
+                let mut land = world.get_mut::<Land>(land)?;
+                land.excavation_mut(tile)?.secure(safety);
+                None
+            }
+            BuildAction::Demolish { building, .. } => {
+                let land = world.get::<Building>(building)?.land;
+                if let Some(mut land) = world.get_mut::<Land>(land) {
+                    land.remove(&building);
+                }
+                world.despawn(building);
+                Some(building)
+            }
+            BuildAction::Move { building, to, .. } => {
+                let mut b = world.get_mut::<Building>(building)?;
+                b.pos = to;
+                b.produced = false;
+                Some(building)
+            }
+        }
+    }
+
+    /// Inverse of `apply`
+    pub fn revert(self, world: &mut World, target: Option<Entity>) -> Reverted {
+        let done = match self {
+            BuildAction::BuyLand { land } => {
+                let Some(mut land) = world.get_mut::<Land>(land) else {
+                    return Reverted::No;
+                };
+
+                if !land.buildings.is_empty() {
+                    return Reverted::No;
+                }
+
+                land.owned = false;
+                true
+            }
+            BuildAction::Build { land, .. } => {
+                let Some(building) = target else {
+                    return Reverted::No;
+                };
+
+                if let Some(mut land) = world.get_mut::<Land>(land) {
+                    land.remove(&building);
+                }
+                world.despawn(building)
+            }
+            BuildAction::Upgrade { building } => {
+                let Some(mut b) = world.get_mut::<Building>(building) else {
+                    return Reverted::No;
+                };
+
+                if b.level <= 1 {
+                    return Reverted::No;
+                }
+
+                b.level -= 1;
+                true
+            }
+            BuildAction::Dig { land, tile } => {
+                // only while the digging is still in progress
+                let Some(mut land) = world.get_mut::<Land>(land) else {
+                    return Reverted::No;
+                };
+
+                let Some(excavation) = land.excavation_mut(tile) else {
+                    return Reverted::No;
+                };
+
+                excavation.progress.take().is_some()
+            }
+            BuildAction::Abandon { .. } | BuildAction::Secure { .. } => false,
+            BuildAction::Demolish { kind, snapshot, .. } => {
+                if tile_taken(world, snapshot.land, snapshot.pos) {
+                    return Reverted::No;
+                }
+
+                let entity = world.spawn((snapshot, kind)).id();
+                return Reverted::Respawned(entity);
+            }
+            BuildAction::Move { building, from, .. } => {
+                let Some(land) = world.get::<Building>(building).map(|b| b.land) else {
+                    return Reverted::No;
+                };
+
+                if tile_taken(world, land, from) {
+                    return Reverted::No;
+                }
+
+                if let Some(mut b) = world.get_mut::<Building>(building) {
+                    b.pos = from;
+                }
+                true
+            }
+        };
+
+        if done { Reverted::Yes } else { Reverted::No }
+    }
+
+    /// Same action pointing to the new entity of a building that came back
+    pub fn retarget(self, old: Entity, new: Entity) -> Self {
+        let swap = |entity: Entity| if entity == old { new } else { entity };
+        match self {
+            BuildAction::Upgrade { building } => BuildAction::Upgrade {
+                building: swap(building),
+            },
+            BuildAction::Demolish {
+                building,
+                kind,
+                snapshot,
+            } => BuildAction::Demolish {
+                building: swap(building),
+                kind,
+                snapshot,
+            },
+            BuildAction::Move { building, from, to } => BuildAction::Move {
+                building: swap(building),
+                from,
+                to,
+            },
+            action => action,
+        }
+    }
+
+    /// Building the action needs to exist, if any
+    pub fn building(&self) -> Option<Entity> {
+        match self {
+            BuildAction::Upgrade { building }
+            | BuildAction::Demolish { building, .. }
+            | BuildAction::Move { building, .. } => Some(*building),
+            _ => None,
+        }
+    }
+}
+
+fn tile_taken(world: &World, land: Entity, tile: UVec2) -> bool {
+    world.get::<Land>(land).is_some_and(|land| {
+        land.buildings
+            .iter()
+            .any(|e| world.get::<Building>(*e).is_some_and(|b| b.pos == tile))
+    })
+}
+
+/// Building placed on the tile of the land
+fn building_at(
+    land: &Land,
+    tile: UVec2,
+    buildings: &Query<(&BuildKind, &Building)>,
+) -> Option<(Entity, BuildKind, Building)> {
+    land.buildings.iter().find_map(|entity| {
+        let (kind, b) = buildings.get(*entity).ok()?;
+        (b.pos == tile).then_some((*entity, *kind, *b))
+    })
+}
+
+fn focused_land<'a>(lands: &'a Query<(Entity, &Land)>) -> Option<(Entity, &'a Land)> {
+    lands.iter().find(|(_, land)| land.focus.is_some())
+}
+
+/// Action that the tool will do on the focused tile and its cost
+fn find_action(
+    tool: Tool,
+    focused: Option<(Entity, &Land)>,
+    buildings: &Query<(&BuildKind, &Building)>,
+    picked: Option<Entity>,
+    index: f32,
+) -> Option<(BuildAction, f32)> {
+    let (land_e, land) = focused?;
+    match tool {
+        Tool::BuyLand => {
+            let available = !land.owned && land.rival.is_none();
+            available.then_some((BuildAction::BuyLand { land: land_e }, land.price))
+        }
+        Tool::Build(kind) => {
+            if !land.owned {
+                return None;
+            }
+
+            let pos = land.focus?;
+            if kind == BuildKind::Mine && land.excavation(pos).abandoned {
+                return None;
+            }
+
+            let existing = land.buildings.iter().find_map(|entity| {
+                let (k, b) = buildings.get(*entity).ok()?;
+                (b.pos == pos).then_some((*entity, *k, b.level))
+            });
+
+            match existing {
+                Some((building, k, level)) if k == kind => Some((
+                    BuildAction::Upgrade { building },
+                    kind.cost(index) * level as f32,
+                )),
+                Some(_) => None,
+                None => Some((
+                    BuildAction::Build {
+                        land: land_e,
+                        pos,
+                        kind,
+                    },
+                    kind.cost(index),
+                )),
+            }
+        }
+        Tool::Dig => {
+            // only empty tiles or the ones with a mine can be dug
+            let tile = land.focus?;
+            let blocked = land.buildings.iter().any(|entity| {
+                buildings
+                    .get(*entity)
+                    .is_ok_and(|(k, b)| b.pos == tile && *k != BuildKind::Mine)
+            });
+            let excavation = land.excavation(tile);
+            (land.owned && !blocked && excavation.can_dig()).then_some((
+                BuildAction::Dig { land: land_e, tile },
+                excavation.dig_cost(index),
+            ))
+        }
+        Tool::Abandon => {
+            let tile = land.focus?;
+            let excavation = land.excavation(tile);
+            (land.owned && excavation.can_abandon())
+                .then_some((BuildAction::Abandon { land: land_e, tile }, 0.0))
+        }
+        Tool::Secure(safety) => {
+            let tile = land.focus?;
+            let excavation = land.excavation(tile);
+            (land.owned && excavation.can_secure(safety)).then_some((
+                BuildAction::Secure {
+                    land: land_e,
+                    tile,
+                    safety,
+                },
+                excavation.secure_cost(safety, index),
+            ))
+        }
+        Tool::Demolish => {
+            // part of the cost comes back, as a negative cost
+            let (building, kind, snapshot) = building_at(land, land.focus?, buildings)?;
+            let refund = kind.cost(index) * snapshot.level as f32 * DEMOLISH_REFUND;
+            land.owned.then_some((
+                BuildAction::Demolish {
+                    building,
+                    kind,
+                    snapshot,
+                },
+                -refund,
+            ))
+        }
+        Tool::Move => {
+            // only to an empty tile of the same land
+            let to = land.focus?;
+            let building = picked?;
+            let (kind, b) = buildings.get(building).ok()?;
+            let excavation = land.excavation(to);
+            let allowed = match kind {
+                BuildKind::Mine => !excavation.abandoned,
+                _ => !excavation.is_digging(),
+            };
+            let free = building_at(land, to, buildings).is_none();
+            (land.owned && b.land == land_e && free && allowed).then_some((
+                BuildAction::Move {
+                    building,
+                    from: b.pos,
+                    to,
+                },
+                kind.cost(index) * MOVE_COST,
+            ))
+        }
+    }
+}
+
+/// Building on the focused tile that the move tool can pick
+fn pickable(
+    focused: Option<(Entity, &Land)>,
+    buildings: &Query<(&BuildKind, &Building)>,
+) -> Option<(Entity, BuildKind)> {
+    let (_, land) = focused?;
+    let (building, kind, _) = building_at(land, land.focus?, buildings)?;
+    land.owned.then_some((building, kind))
+}
+
+fn init_build_system(mut cmds: Commands) {
+    cmds.insert_resource(MovePick::default());
+}
+
+pub fn on_tool_click_system(
+    In(entity): In<Entity>,
+    mut cmds: Commands,
+    tools: Query<&Tool>,
+    lands: Query<(Entity, &Land)>,
+    buildings: Query<(&BuildKind, &Building)>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+    mut shake: ResMut<CamShake>,
+    mut picked: ResMut<MovePick>,
+    mut notifications: ResMut<Notifications>,
+    inflation: Res<Inflation>,
+) {
+    let Ok(tool) = tools.get(entity) else {
+        return;
+    };
+
+    // the move tool picks the building first and moves it on the next click
+    let focused = focused_land(&lands);
+    let pick = (*tool == Tool::Move)
+        .then(|| pickable(focused, &buildings))
+        .flatten();
+    if let Some((building, kind)) = pick {
+        picked.0 = Some(building);
+        notifications.push(
+            format!("Pick an empty tile for the {}", kind.name()),
+            PICO8_WHITE,
+            None,
+        );
+        return;
+    }
+
+    let Some((action, cost)) = find_action(*tool, focused, &buildings, picked.0, inflation.index)
+    else {
+        return;
+    };
+
+    if money.0 < cost {
+        return;
+    }
+
+    money.0 -= cost;
+    ledger.money(LedgerCategory::Construction, -cost);
+    shake.trade(cost);
+    if matches!(action, BuildAction::Move { .. }) {
+        picked.0 = None;
+    }
+
+    cmds.queue(move |world: &mut World| {
+        let target = action.apply(world);
+        let tick = world.resource::<SimClock>().tick;
+        world.resource_mut::<UndoStack>().push(UndoEntry {
+            action,
+            cost,
+            target,
+            tick,
+        });
+    });
+}
+
+fn update_tool_btns_system(
+    mut btns: Query<(&mut UIImgButton, &Tool)>,
+    lands: Query<(Entity, &Land)>,
+    buildings: Query<(&BuildKind, &Building)>,
+    money: Res<Money>,
+    picked: Res<MovePick>,
+    inflation: Res<Inflation>,
+) {
+    let focused = focused_land(&lands);
+    btns.iter_mut().for_each(|(mut btn, tool)| {
+        let can_pick = *tool == Tool::Move && pickable(focused, &buildings).is_some();
+        let enabled = can_pick
+            || find_action(*tool, focused, &buildings, picked.0, inflation.index)
+                .is_some_and(|(_, cost)| money.0 >= cost);
+        if btn.enabled != enabled {
+            btn.enabled = enabled;
+        }
+    });
+}
@@ -0,0 +1,158 @@
+use rkit::{gfx::Color, prelude::*};
+
+use crate::{
+    consts::*,
+    economy::{SimClock, SimSet},
+    game::BuildKind,
+    notify::Notifications,
+    screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+};
+
+// Salt to roll the weather of each day
+const WEATHER_SALT: u64 = 0x57_0A3;
+
+pub fn calendar_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_calendar_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::World.on_tick((calendar_system, weather_system).chain()),
+        )
+        .add_screen_systems(screen, OnUpdate, update_calendar_text_system);
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    fn from_day(day: u32) -> Self {
+        match (day / SEASON_DAYS) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+
+    /// Production multiplier of the season for the building
+    pub fn multiplier(&self, kind: BuildKind) -> f32 {
+        match (self, kind) {
+            (Season::Spring, BuildKind::Forest) => 1.5,
+            (Season::Summer, BuildKind::Farm) => 1.2,
+            (Season::Autumn, BuildKind::Farm) => 1.1,
+            (Season::Winter, BuildKind::Farm) => 0.3,
+            (Season::Winter, BuildKind::Forest) => 0.7,
+            _ => 1.0,
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            Season::Spring => PICO8_GREEN,
+            Season::Summer => PICO8_YELLOW,
+            Season::Autumn => PICO8_ORANGE,
+            Season::Winter => PICO8_WHITE,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Calendar {
+    pub day: u32,
+    pub season: Season,
+    /// Ticks left for the current storm to pass
+    pub storm: u32,
+}
+
+impl Calendar {
+    pub fn multiplier(&self, kind: BuildKind) -> f32 {
+        self.season.multiplier(kind)
+    }
+
+    /// Storms stop the mines until they pass
+    pub fn halts(&self, kind: BuildKind) -> bool {
+        self.storm > 0 && matches!(kind, BuildKind::Mine)
+    }
+
+    pub fn label(&self) -> String {
+        let label = format!("{}, Day {}", self.season.name(), self.day + 1);
+        if self.storm > 0 {
+            format!("{label} - Storm")
+        } else {
+            label
+        }
+    }
+}
+
+// - systems
+fn init_calendar_system(mut cmds: Commands) {
+    cmds.insert_resource(Calendar::default());
+}
+
+fn calendar_system(mut calendar: ResMut<Calendar>, clock: Res<SimClock>) {
+    let day = (clock.tick / DAY_TICKS as u64) as u32;
+    if calendar.day == day {
+        return;
+    }
+
+    calendar.day = day;
+    calendar.season = Season::from_day(day);
+}
+
+fn weather_system(
+    mut calendar: ResMut<Calendar>,
+    clock: Res<SimClock>,
+    seed: Res<GameSeed>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if calendar.storm > 0 {
+        calendar.storm -= 1;
+        if calendar.storm == 0 {
+            notifications.push("The storm has passed", PICO8_BLUE, None);
+        }
+        return;
+    }
+
+    // the weather is rolled once at the start of each day
+    let new_day = clock.tick % DAY_TICKS as u64 == 0;
+    if !new_day {
+        return;
+    }
+
+    let mut rng = SeedRng::fork(seed.value ^ WEATHER_SALT, calendar.day as u64);
+    if rng.chance(STORM_CHANCE) {
+        calendar.storm = rng.range(STORM_MIN_TICKS, STORM_MAX_TICKS);
+        notifications.push("A storm halts the mines", PICO8_INDIGO, None);
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct CalendarText;
+
+fn update_calendar_text_system(
+    text: Single<&mut UIText, With<CalendarText>>,
+    calendar: Res<Calendar>,
+) {
+    if !calendar.is_changed() {
+        return;
+    }
+
+    text.into_inner().text = calendar.label();
+}
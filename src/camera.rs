@@ -1,15 +1,37 @@
 use rkit::{
     draw::{BaseCam2D, Camera2D, ScreenMode},
-    math::{Rect, Vec2},
+    math::{IVec2, Rect, Vec2},
     prelude::*,
 };
 
-use crate::{components::Pos, consts::*};
+use crate::{
+    components::Pos,
+    consts::*,
+    game::Land,
+    input::{Pointer, update_pointer_system},
+    screens::AppScreen,
+    seed::SeedRng,
+    settings::{Scaling, Settings},
+    tween::{Easing, PosTween},
+    worldgen::cell_pos,
+};
 
 pub fn camera_plugin(app: &mut App) {
-    app.add_systems(OnSetup, init_cam_system)
-        .add_systems(OnPreUpdate, update_camera_system)
-        .add_systems(OnPreRender, update_camera_system);
+    app.add_resource(CamShake::default())
+        .add_systems(OnSetup, init_cam_system)
+        .add_systems(
+            OnPreUpdate,
+            (
+                apply_display_settings_system,
+                shake_system,
+                update_camera_system,
+            )
+                .chain()
+                .after(update_pointer_system),
+        )
+        .add_systems(OnPreRender, update_camera_system)
+        .add_systems(OnEnter(AppScreen::Game), reset_game_cam_system)
+        .add_screen_systems(AppScreen::Game, OnUpdate, control_game_cam_system);
 }
 
 #[derive(Component, Clone, Copy)]
@@ -18,11 +40,70 @@ pub struct GameCam;
 #[derive(Component, Clone, Copy)]
 pub struct UICam;
 
+/// Player controls of the game camera
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CamControl {
+    /// Integer to keep the pixel-perfect rendering
+    pub zoom: u32,
+    /// Last mouse position while dragging
+    drag: Option<Vec2>,
+    /// Last land clicked and when, to detect double clicks
+    last_click: Option<(Entity, f32)>,
+    /// Zoom from and to while gliding with a `PosTween`
+    zoom_tween: Option<(u32, u32)>,
+}
+
+impl Default for CamControl {
+    fn default() -> Self {
+        Self {
+            zoom: CAM_MIN_ZOOM,
+            drag: None,
+            last_click: None,
+            zoom_tween: None,
+        }
+    }
+}
+
+/// Trauma based shake added on top of the game camera position
+#[derive(Resource)]
+pub struct CamShake {
+    /// From 0 to 1, decays over time
+    pub trauma: f32,
+    offset: Vec2,
+    rng: SeedRng,
+}
+
+impl Default for CamShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            offset: Vec2::ZERO,
+            rng: SeedRng::new(0),
+        }
+    }
+}
+
+impl CamShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Shakes a bit for the trades big enough to notice
+    pub fn trade(&mut self, money: f32) {
+        if money.abs() >= SHAKE_BIG_TRADE {
+            self.add_trauma(SHAKE_TRADE_TRAUMA);
+        }
+    }
+}
+
 #[derive(Component, Deref)]
 pub struct Cam {
     #[deref]
     pub cam: Camera2D,
     pub mouse_pos: Vec2,
+    /// Internal resolution
+    pub resolution: Vec2,
+    pub scaling: Scaling,
 }
 
 impl Cam {
@@ -34,6 +115,8 @@ impl Cam {
         Self {
             cam,
             mouse_pos: Vec2::ZERO,
+            resolution: res,
+            scaling: Scaling::AspectFit,
         }
     }
 
@@ -43,11 +126,35 @@ impl Cam {
         Rect::new(rect.origin - offset, rect.size + offset * 2.0)
     }
 
-    #[inline]
-    pub fn set_resolution(&mut self, res: Vec2) {
-        self.cam.set_screen_mode(ScreenMode::AspectFit(res));
+    pub fn set_display(&mut self, res: Vec2, scaling: Scaling) {
+        self.resolution = res;
+        self.scaling = scaling;
+
+        // integer scaling uses the window pixels and zooms by whole steps
+        let mode = match scaling {
+            Scaling::AspectFit => ScreenMode::AspectFit(res),
+            Scaling::Integer => ScreenMode::Basic,
+            Scaling::Stretch => ScreenMode::Fill(res),
+        };
+        self.cam.set_screen_mode(mode);
         self.cam.update();
     }
+
+    /// Extra zoom needed by the scaling mode to fill the window
+    fn scale_factor(&self, win_size: Vec2) -> f32 {
+        match self.scaling {
+            Scaling::Integer => (win_size / self.resolution).min_element().floor().max(1.0),
+            _ => 1.0,
+        }
+    }
+
+    /// Area seen without zoom, the integer scaling shows the whole window
+    fn view_size(&self, win_size: Vec2) -> Vec2 {
+        match self.scaling {
+            Scaling::Integer => win_size / self.scale_factor(win_size),
+            _ => self.resolution,
+        }
+    }
 }
 
 fn init_cam_system(mut cmds: Commands, win: Res<Window>) {
@@ -55,6 +162,7 @@ fn init_cam_system(mut cmds: Commands, win: Res<Window>) {
         GameCam,
         Cam::new(win.size(), RESOLUTION),
         Pos(RESOLUTION * 0.5),
+        CamControl::default(),
     ));
 
     cmds.spawn((
@@ -64,15 +172,236 @@ fn init_cam_system(mut cmds: Commands, win: Res<Window>) {
     ));
 }
 
-fn update_camera_system(
-    mut cams_query: Query<(&mut Cam, &Pos)>,
+pub fn update_camera_system(
+    mut cams_query: Query<(&mut Cam, &Pos, Option<&CamControl>)>,
     win: Res<Window>,
-    mouse: Res<Mouse>,
+    pointer: Res<Pointer>,
+    shake: Res<CamShake>,
 ) {
-    for (mut cam, pos) in &mut cams_query {
-        cam.mouse_pos = cam.screen_to_local(mouse.position());
+    for (mut cam, pos, control) in &mut cams_query {
+        // only the game camera is controlled and shaken
+        let (zoom, offset) = control.map_or((1, Vec2::ZERO), |c| (c.zoom, shake.offset));
+        let scale = cam.scale_factor(win.size());
+        cam.mouse_pos = cam.screen_to_local(pointer.position);
         cam.set_size(win.size());
-        cam.set_position(pos.0);
+        cam.set_zoom(zoom as f32 * scale);
+        cam.set_position(pos.0 + offset);
         cam.update();
     }
 }
+
+fn apply_display_settings_system(
+    mut cams: Query<(&mut Cam, &mut Pos, Has<UICam>)>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    // the ui is anchored to the top left corner of its resolution
+    cams.iter_mut().for_each(|(mut cam, mut pos, is_ui)| {
+        let preset = settings.resolution;
+        if is_ui {
+            cam.set_display(preset.ui_size(), settings.scaling);
+            pos.0 = preset.ui_size() * 0.5;
+        } else {
+            cam.set_display(preset.size(), settings.scaling);
+        }
+    });
+}
+
+fn shake_system(mut shake: ResMut<CamShake>, settings: Res<Settings>, time: Res<Time>) {
+    if shake.trauma <= 0.0 {
+        return;
+    }
+
+    if settings.reduced_motion {
+        shake.trauma = 0.0;
+        shake.offset = Vec2::ZERO;
+        return;
+    }
+
+    shake.trauma = (shake.trauma - SHAKE_DECAY * time.delta_f32()).max(0.0);
+
+    // rounded to whole pixels to keep the pixel-perfect camera aligned
+    let amount = SHAKE_MAX_OFFSET * shake.trauma * shake.trauma;
+    let dir = Vec2::new(shake.rng.next_f32(), shake.rng.next_f32()) * 2.0 - 1.0;
+    shake.offset = (dir * amount).round();
+}
+
+fn reset_game_cam_system(cam: Single<(&mut Pos, &mut CamControl), With<GameCam>>) {
+    // the game starts looking at the home land
+    let (mut pos, mut control) = cam.into_inner();
+    pos.0 = cell_pos(IVec2::ZERO);
+    *control = CamControl::default();
+}
+
+/// Glides the game camera to the point, optionally changing the zoom
+pub fn focus_game_cam(target: Vec2, zoom: Option<u32>) -> impl FnOnce(&mut World) {
+    move |world: &mut World| {
+        let bounds = world
+            .query::<(&Land, &Pos)>()
+            .iter(world)
+            .map(|(land, pos)| land.bounds(pos.0))
+            .collect::<Vec<_>>();
+        let win_size = world.resource::<Window>().size();
+
+        let mut query =
+            world.query_filtered::<(Entity, &Cam, &Pos, &mut CamControl), With<GameCam>>();
+        let Ok((entity, cam, pos, mut control)) = query.get_single_mut(world) else {
+            return;
+        };
+
+        // the zoom follows the glide, see `control_game_cam_system`
+        let zoom = zoom.map_or(control.zoom, |z| z.clamp(CAM_MIN_ZOOM, CAM_MAX_ZOOM));
+        control.zoom_tween = (zoom != control.zoom).then_some((control.zoom, zoom));
+
+        let view = cam.view_size(win_size);
+        let to = clamp_to_bounds(target, zoom, view, &bounds);
+        let tween = PosTween::new(pos.0, to, CAM_FOCUS_TIME, Easing::CubicInOut);
+        world.entity_mut(entity).insert(tween);
+    }
+}
+
+fn control_game_cam_system(
+    mut cmds: Commands,
+    cam: Single<(Entity, &Cam, &mut Pos, &mut CamControl, Option<&PosTween>), With<GameCam>>,
+    lands: Query<(Entity, &Land, &Pos), Without<GameCam>>,
+    mouse: Res<Mouse>,
+    pointer: Res<Pointer>,
+    keyboard: Res<Keyboard>,
+    time: Res<Time>,
+    win: Res<Window>,
+) {
+    let (entity, cam, mut pos, mut control, tween) = cam.into_inner();
+
+    // the zoom steps with the glide, whole steps keep it pixel-perfect
+    if let Some((from, to)) = control.zoom_tween {
+        match tween {
+            Some(tween) => {
+                let t = tween.easing.apply(tween.progress());
+                control.zoom = (from as f32 + (to as f32 - from as f32) * t).round() as u32;
+            }
+            None => {
+                control.zoom = to;
+                control.zoom_tween = None;
+            }
+        }
+    }
+
+    let zoom = control.zoom as f32;
+    let bounds = lands
+        .iter()
+        .map(|(_, land, pos)| land.bounds(pos.0))
+        .collect::<Vec<_>>();
+
+    // double click or tap on a land to center and zoom on it
+    if pointer.click {
+        let now = time.elapsed_f32();
+        let clicked = lands
+            .iter()
+            .find(|(_, land, pos)| land.bounds(pos.0).contains(cam.mouse_pos));
+        let is_double = clicked.is_some_and(|(land_e, ..)| {
+            control
+                .last_click
+                .is_some_and(|(e, t)| e == land_e && now - t <= CAM_DOUBLE_CLICK_TIME)
+        });
+
+        if let Some((land_e, _, land_pos)) = clicked {
+            if is_double {
+                control.last_click = None;
+                cmds.queue(focus_game_cam(land_pos.0, Some(CAM_FOCUS_ZOOM)));
+                return;
+            }
+
+            control.last_click = Some((land_e, now));
+        }
+    }
+
+    // keyboard, faster when zoomed out
+    let mut dir = Vec2::ZERO;
+    if keyboard.is_down(KeyCode::KeyA) || keyboard.is_down(KeyCode::ArrowLeft) {
+        dir.x -= 1.0;
+    }
+    if keyboard.is_down(KeyCode::KeyD) || keyboard.is_down(KeyCode::ArrowRight) {
+        dir.x += 1.0;
+    }
+    if keyboard.is_down(KeyCode::KeyW) || keyboard.is_down(KeyCode::ArrowUp) {
+        dir.y -= 1.0;
+    }
+    if keyboard.is_down(KeyCode::KeyS) || keyboard.is_down(KeyCode::ArrowDown) {
+        dir.y += 1.0;
+    }
+    let mut next = pos.0 + dir.normalize_or_zero() * CAM_PAN_SPEED * time.delta_f32() / zoom;
+
+    // drag with the right or middle button, the point grabbed stays under the mouse
+    let dragging = mouse.is_down(MouseButton::Right) || mouse.is_down(MouseButton::Middle);
+    let mouse_pos = mouse.position();
+    if dragging {
+        if let Some(last) = control.drag {
+            next += cam.screen_to_local(last) - cam.screen_to_local(mouse_pos);
+        }
+        control.drag = Some(mouse_pos);
+    } else {
+        control.drag = None;
+    }
+
+    // one finger drag, unless it started on the ui
+    if pointer.pan != Vec2::ZERO && pointer.ui_grab.is_none() {
+        next += cam.screen_to_local(Vec2::ZERO) - cam.screen_to_local(pointer.pan);
+    }
+
+    // wheel or pinch zoom keeping the point under the pointer
+    if pointer.zoom != 0 {
+        let new_zoom = control
+            .zoom
+            .saturating_add_signed(pointer.zoom)
+            .clamp(CAM_MIN_ZOOM, CAM_MAX_ZOOM);
+        if new_zoom != control.zoom {
+            let point = cam.screen_to_local(pointer.position);
+            next = point + (next - point) * zoom / new_zoom as f32;
+            control.zoom = new_zoom;
+        }
+    }
+
+    // the player takes back the control from any glide in progress
+    let moved = next != pos.0 || pointer.zoom != 0;
+    if tween.is_some() {
+        if !moved {
+            return;
+        }
+
+        cmds.entity(entity).remove::<PosTween>();
+        control.zoom_tween = None;
+    }
+
+    let view = cam.view_size(win.size());
+    pos.0 = clamp_to_bounds(next, control.zoom, view, &bounds);
+}
+
+/// Keeps the view inside the bounds, centered if they are smaller than the view
+fn clamp_to_bounds(pos: Vec2, zoom: u32, view: Vec2, bounds: &[Rect]) -> Vec2 {
+    let Some((min, max)) = bounds
+        .iter()
+        .map(|rect| (rect.min(), rect.min() + rect.size))
+        .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+    else {
+        return pos;
+    };
+
+    let half_view = view * 0.5 / zoom as f32;
+    let center = (min + max) * 0.5;
+    let min = min - LAND_GAP + half_view;
+    let max = max + LAND_GAP - half_view;
+    let x = if min.x > max.x {
+        center.x
+    } else {
+        pos.x.clamp(min.x, max.x)
+    };
+    let y = if min.y > max.y {
+        center.y
+    } else {
+        pos.y.clamp(min.y, max.y)
+    };
+    Vec2::new(x, y)
+}
@@ -36,3 +36,171 @@ pub const LAND_SIZE: Vec2 = Vec2::splat(3.0);
 
 // Minimum time to load the bar
 pub const LOAD_MIN_TIME: f32 = 1.0;
+
+// Seconds between simulation ticks
+pub const SIM_TICK_TIME: f32 = 1.0;
+
+// Units of each resource that a shop can sell per tick
+pub const SHOP_SELL_RATE: f32 = 5.0;
+
+// Market price changes
+pub const MARKET_PRICE_IMPACT: f32 = 0.01;
+pub const MARKET_MIN_PRICE_FACTOR: f32 = 0.25;
+pub const MARKET_MAX_PRICE_FACTOR: f32 = 3.0;
+pub const MARKET_RECOVER_RATE: f32 = 0.05;
+
+// World generation
+pub const WORLD_GRID: UVec2 = UVec2::new(5, 3);
+pub const WORLD_CELL_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+pub const WORLD_LAND_CHANCE: f32 = 0.7;
+pub const LAND_BASE_PRICE: f32 = 150.0;
+
+// Extra storage per warehouse level, relative to the base capacity
+pub const WAREHOUSE_CAPACITY_BONUS: f32 = 0.5;
+
+// Ratio of the capacity at which the counters warn the player
+pub const STORAGE_WARNING_RATIO: f32 = 0.9;
+
+// Notifications
+pub const NOTIFICATION_TIME: f32 = 5.0;
+pub const NOTIFICATION_MAX: usize = 4;
+
+// Money at the start of the game
+pub const STARTING_MONEY: f32 = 100.0;
+
+// Ticks in debt before going bankrupt
+pub const BANKRUPTCY_TICKS: u32 = 60;
+
+// Ticks of upkeep needed in the bank to restart a building
+pub const RESTART_UPKEEP_RESERVE: f32 = 30.0;
+
+// Bank
+pub const LOAN_PERIOD_TICKS: u32 = 30;
+pub const MAX_LOANS: usize = 3;
+pub const LATE_PAYMENT_FEE: f32 = 0.1;
+pub const CREDIT_MAX: f32 = 100.0;
+pub const CREDIT_MISSED_PENALTY: f32 = 15.0;
+pub const CREDIT_PAYMENT_BONUS: f32 = 2.0;
+// Extra interest per period with the worst credit score
+pub const CREDIT_RATE_PENALTY: f32 = 0.08;
+
+// Taxes and happiness
+pub const DEFAULT_TAX_RATE: f32 = 0.1;
+pub const MAX_TAX_RATE: f32 = 0.5;
+pub const TAX_PER_PERSON: f32 = 0.2;
+pub const TAX_UNHAPPINESS: f32 = 1.2;
+pub const HAPPINESS_BASE: f32 = 0.6;
+pub const HAPPINESS_CHANGE_RATE: f32 = 0.1;
+
+// Pollution
+pub const POLLUTION_SPREAD: f32 = 0.2;
+pub const POLLUTION_LAND_SPREAD: f32 = 0.02;
+pub const POLLUTION_DECAY: f32 = 0.01;
+pub const POLLUTION_YIELD_PENALTY: f32 = 0.6;
+pub const POLLUTION_UNHAPPINESS: f32 = 0.4;
+
+// Calendar and weather
+pub const DAY_TICKS: u32 = 10;
+pub const SEASON_DAYS: u32 = 6;
+pub const STORM_CHANCE: f32 = 0.15;
+pub const STORM_MIN_TICKS: u32 = 5;
+pub const STORM_MAX_TICKS: u32 = 15;
+
+// Minutes of history kept by the ledger
+pub const LEDGER_HISTORY_MINUTES: usize = 10;
+
+// Stats history
+pub const STATS_INTERVAL_TICKS: u32 = 5;
+pub const STATS_CAPACITY: usize = 120;
+
+// Prestige
+pub const PRESTIGE_MILESTONE: f32 = 5000.0;
+pub const PRESTIGE_YIELD_BONUS: f32 = 0.1;
+pub const PRESTIGE_FUNDS_BONUS: f32 = 50.0;
+
+// Undo
+pub const UNDO_MAX: usize = 20;
+pub const UNDO_WINDOW_TICKS: u64 = 10;
+
+// Part of the building cost refunded when demolished, for each level
+pub const DEMOLISH_REFUND: f32 = 0.5;
+// Part of the building cost paid to move it
+pub const MOVE_COST: f32 = 0.25;
+
+// Automation rules
+pub const MAX_RULES: usize = 8;
+pub const RULE_THRESHOLD_STEP: f32 = 10.0;
+pub const RULE_PRICE_STEP: f32 = 0.5;
+
+// Logistics
+pub const TRANSPORT_RATE: f32 = 5.0;
+pub const TRANSPORT_SPEED: f32 = 32.0;
+pub const MAX_ROUTES: usize = 8;
+
+// Rivals
+pub const RIVAL_STARTING_MONEY: f32 = 200.0;
+pub const RIVAL_UPKEEP: f32 = 0.5;
+pub const RIVAL_MIN_PRICE_FACTOR: f32 = 0.5;
+pub const RIVAL_LAND_BUDGET: f32 = 0.8;
+
+// Contracts
+pub const CONTRACT_OFFERS: usize = 3;
+pub const MAX_CONTRACTS: usize = 4;
+pub const CONTRACT_PENALTY_FACTOR: f32 = 0.5;
+pub const CONTRACT_MIN_DAYS: u32 = 2;
+pub const CONTRACT_MAX_DAYS: u32 = 5;
+
+// Inflation, rates are per day
+pub const INFLATION_MONEY_SCALE: f32 = 2000.0;
+pub const INFLATION_ADJUST: f32 = 0.05;
+pub const INFLATION_MIN_RATE: f32 = -0.02;
+pub const INFLATION_MAX_RATE: f32 = 0.05;
+pub const INFLATION_MIN_INDEX: f32 = 0.5;
+pub const INFLATION_MAX_INDEX: f32 = 4.0;
+
+// Excavation
+pub const DIG_COST: f32 = 25.0;
+pub const DIG_TICKS: u32 = 8;
+pub const DEPOSIT_CHANCE: f32 = 0.6;
+pub const DEPOSIT_YIELD: f32 = 2.0;
+pub const DEPOSIT_MIN_SIZE: u32 = 100;
+pub const DEPOSIT_MAX_SIZE: u32 = 300;
+
+// Hazards, the daily risk grows with the depth from the min depth
+pub const FLOOD_MIN_DEPTH: u32 = 2;
+pub const FLOOD_BASE_RISK: f32 = 0.05;
+pub const FLOOD_RISK_PER_DEPTH: f32 = 0.04;
+pub const GAS_MIN_DEPTH: u32 = 3;
+pub const GAS_BASE_RISK: f32 = 0.04;
+pub const GAS_RISK_PER_DEPTH: f32 = 0.03;
+pub const CAVE_IN_MIN_DEPTH: u32 = 1;
+pub const CAVE_IN_BASE_RISK: f32 = 0.02;
+pub const CAVE_IN_RISK_PER_DEPTH: f32 = 0.03;
+pub const HAZARD_MAX_RISK: f32 = 0.5;
+pub const CAVE_IN_TICKS: u32 = 30;
+pub const SUPPORTS_RISK_FACTOR: f32 = 0.5;
+pub const SUPPORTS_MAX_LEVEL: u32 = 3;
+pub const PUMP_COST: f32 = 40.0;
+pub const VENTILATION_COST: f32 = 35.0;
+pub const SUPPORTS_COST: f32 = 20.0;
+
+// Camera
+pub const CAM_MIN_ZOOM: u32 = 1;
+pub const CAM_MAX_ZOOM: u32 = 4;
+pub const CAM_PAN_SPEED: f32 = 200.0;
+pub const CAM_FOCUS_TIME: f32 = 0.6;
+pub const CAM_FOCUS_ZOOM: u32 = 3;
+pub const CAM_DOUBLE_CLICK_TIME: f32 = 0.3;
+pub const MINIMAP_SIZE: Vec2 = Vec2::new(64.0, 40.0);
+
+// Camera shake, the offset grows with the square of the trauma
+pub const SHAKE_MAX_OFFSET: f32 = 4.0;
+pub const SHAKE_DECAY: f32 = 1.5;
+pub const SHAKE_CAVE_IN_TRAUMA: f32 = 0.6;
+pub const SHAKE_TRADE_TRAUMA: f32 = 0.25;
+pub const SHAKE_BIG_TRADE: f32 = 150.0;
+
+// Touch
+pub const TOUCH_TAP_DISTANCE: f32 = 8.0;
+pub const TOUCH_LONG_PRESS_TIME: f32 = 0.5;
+pub const TOUCH_PINCH_STEP: f32 = 1.25;
@@ -0,0 +1,571 @@
+use rkit::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    calendar::Calendar,
+    consts::*,
+    economy::{Market, Money, SimSet, Stock, TotalStock},
+    game::{Land, ResourceKind},
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    rivals::Rival,
+    screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+    ui::{
+        UIGameLayout,
+        btns::{UITextButton, create_text_btn},
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+};
+
+// Salt to roll the offers of each day
+const OFFERS_SALT: u64 = 0xC0_471C;
+
+pub fn contracts_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_contracts_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Automate.on_tick((contracts_system, reserve_system).chain()),
+        )
+        .add_screen_systems(screen, OnUpdate, update_desk_panel_system);
+}
+
+/// Promise to deliver the amount of a resource at a fixed price
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contract {
+    pub kind: ResourceKind,
+    pub amount: f32,
+    /// Price per unit
+    pub price: f32,
+    pub due_day: u32,
+}
+
+impl Contract {
+    pub fn value(&self) -> f32 {
+        self.amount * self.price
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{:.0} {} at ${:.2} on day {}",
+            self.amount,
+            self.kind.name(),
+            self.price,
+            self.due_day + 1
+        )
+    }
+}
+
+/// Result of closing a contract
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settlement {
+    pub delivered: f32,
+    pub payment: f32,
+    pub penalty: f32,
+}
+
+/// Delivers as much as possible, the missing units are paid as a penalty
+pub fn settle(contract: &Contract, available: f32) -> Settlement {
+    let delivered = available.max(0.0).min(contract.amount).floor();
+    let missing = contract.amount - delivered;
+    Settlement {
+        delivered,
+        payment: delivered * contract.price,
+        penalty: missing * contract.price * CONTRACT_PENALTY_FACTOR,
+    }
+}
+
+/// Units taken from each stock, in order, to reach the amount
+pub fn draw_down(stocks: &[f32], amount: f32) -> Vec<f32> {
+    let mut remaining = amount.max(0.0);
+    stocks
+        .iter()
+        .map(|stock| {
+            let taken = stock.max(0.0).min(remaining);
+            remaining -= taken;
+            taken
+        })
+        .collect()
+}
+
+/// Offers of the day priced over the base prices, not the traded ones, so
+/// the same seed, day and price index always give the same offers
+pub fn roll_offers(seed: u64, day: u32, index: f32) -> Vec<Contract> {
+    let mut rng = SeedRng::fork(seed ^ OFFERS_SALT, day as u64);
+    let kinds = ResourceKind::iter()
+        .filter(|kind| kind.is_tradable())
+        .collect::<Vec<_>>();
+    (0..CONTRACT_OFFERS)
+        .map(|_| {
+            let kind = kinds[rng.range(0, kinds.len() as u32) as usize];
+            let amount = (rng.range(2, 10) * 10) as f32;
+            let premium = 1.1 + rng.next_f32() * 0.3;
+            let days = rng.range(CONTRACT_MIN_DAYS, CONTRACT_MAX_DAYS + 1);
+            Contract {
+                kind,
+                amount,
+                price: (kind.base_price() * index * premium * 100.0).round() / 100.0,
+                due_day: day + days,
+            }
+        })
+        .collect()
+}
+
+#[derive(Resource, Default)]
+pub struct Contracts {
+    pub offers: Vec<Contract>,
+    pub signed: Vec<Contract>,
+    day: Option<u32>,
+}
+
+impl Contracts {
+    /// Units of the resource promised by the signed contracts
+    pub fn reserved(&self, kind: ResourceKind) -> f32 {
+        self.signed
+            .iter()
+            .filter(|contract| contract.kind == kind)
+            .map(|contract| contract.amount)
+            .sum()
+    }
+}
+
+/// Owned lands in a fixed order so the deliveries are always the same
+fn owned_stocks<'a>(
+    lands: impl Iterator<Item = (Entity, &'a Land, Mut<'a, Stock>)>,
+) -> Vec<(Entity, Mut<'a, Stock>)> {
+    let mut owned = lands
+        .filter(|(_, land, _)| land.owned)
+        .map(|(e, _, stock)| (e, stock))
+        .collect::<Vec<_>>();
+    owned.sort_by_key(|(e, _)| *e);
+    owned
+}
+
+// - systems
+fn init_contracts_system(mut cmds: Commands) {
+    cmds.insert_resource(Contracts::default());
+}
+
+fn contracts_system(
+    mut contracts: ResMut<Contracts>,
+    mut lands: Query<(Entity, &Land, &mut Stock)>,
+    mut rivals: Query<(Entity, &mut Rival)>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+    mut notifications: ResMut<Notifications>,
+    calendar: Res<Calendar>,
+    market: Res<Market>,
+    seed: Res<GameSeed>,
+) {
+    let day = calendar.day;
+    if contracts.day == Some(day) {
+        return;
+    }
+    contracts.day = Some(day);
+
+    // rivals sign the offers left by the player, the one focused on
+    // the resource first
+    let mut rival_list = rivals.iter_mut().collect::<Vec<_>>();
+    rival_list.sort_by_key(|(e, _)| *e);
+    let left = std::mem::take(&mut contracts.offers);
+    left.iter().for_each(|offer| {
+        let idx = rival_list
+            .iter()
+            .position(|(_, r)| r.profile.focus == offer.kind)
+            .or((!rival_list.is_empty()).then_some(0));
+        if let Some((_, rival)) = idx.and_then(|i| rival_list.get_mut(i)) {
+            rival.money += offer.value() - offer.amount * market.price(offer.kind);
+            notifications.push(
+                format!(
+                    "{} signed the {} contract",
+                    rival.profile.name,
+                    offer.kind.name()
+                ),
+                rival.color,
+                None,
+            );
+        }
+    });
+
+    contracts.offers = roll_offers(seed.value, day, market.index);
+
+    let mut owned = owned_stocks(lands.iter_mut());
+
+    let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut contracts.signed)
+        .into_iter()
+        .partition(|contract| contract.due_day <= day);
+    contracts.signed = pending;
+
+    due.into_iter().for_each(|contract| {
+        let stocks = owned
+            .iter()
+            .map(|(_, s)| s.get(contract.kind))
+            .collect::<Vec<_>>();
+        let result = settle(&contract, stocks.iter().sum());
+        draw_down(&stocks, result.delivered)
+            .into_iter()
+            .zip(owned.iter_mut())
+            .for_each(|(amount, (_, stock))| {
+                stock.take(contract.kind, amount);
+            });
+
+        money.0 += result.payment - result.penalty;
+        ledger.money(LedgerCategory::Contracts, result.payment);
+        ledger.money(LedgerCategory::Events, -result.penalty);
+        ledger.resource(LedgerCategory::Contracts, contract.kind, -result.delivered);
+
+        if result.penalty > 0.0 {
+            notifications.push(
+                format!(
+                    "{} contract failed, ${:.0} penalty",
+                    contract.kind.name(),
+                    result.penalty
+                ),
+                PICO8_RED,
+                None,
+            );
+        } else {
+            notifications.push(
+                format!("{} contract delivered", contract.kind.name()),
+                PICO8_GREEN,
+                None,
+            );
+        }
+    });
+}
+
+/// Holds back the units of the signed contracts from the lands, in the
+/// order they are delivered
+pub fn reserve_system(contracts: Res<Contracts>, mut lands: Query<(Entity, &Land, &mut Stock)>) {
+    let mut owned = owned_stocks(lands.iter_mut());
+    owned
+        .iter_mut()
+        .for_each(|(_, stock)| stock.clear_reserved());
+
+    ResourceKind::iter().for_each(|kind| {
+        let reserved = contracts.reserved(kind);
+        if reserved <= 0.0 {
+            return;
+        }
+
+        let stocks = owned.iter().map(|(_, s)| s.get(kind)).collect::<Vec<_>>();
+        draw_down(&stocks, reserved)
+            .into_iter()
+            .zip(owned.iter_mut())
+            .for_each(|(amount, (_, stock))| stock.reserve(kind, amount));
+    });
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct DeskPanel;
+
+#[derive(Component, Clone, Copy)]
+struct OfferText(usize);
+
+#[derive(Component, Clone, Copy)]
+struct SignBtn(usize);
+
+#[derive(Component, Clone, Copy)]
+struct SignedText(usize);
+
+pub fn create_desk_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Desk",
+        48.0,
+        UIOnClick::run(toggle_desk_panel_system),
+    )
+}
+
+fn toggle_desk_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<DeskPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Contracts", DeskPanel);
+        spawn_panel_node(cmds, panel, content, panel_text("Offers", PICO8_INDIGO));
+        (0..CONTRACT_OFFERS).for_each(|idx| {
+            let row = spawn_panel_node(cmds, panel, content, panel_row());
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (OfferText(idx), panel_text("", PICO8_LIGHT_GRAY)),
+            );
+            let btn = create_text_btn(
+                cmds,
+                UIGameLayout,
+                "Sign",
+                32.0,
+                (PanelNode(panel), SignBtn(idx), UIOnClick::run(sign_system)),
+            );
+            cmds.add_ui_child(UIGameLayout, row, btn);
+        });
+
+        spawn_panel_node(cmds, panel, content, panel_text("Signed", PICO8_INDIGO));
+        (0..MAX_CONTRACTS).for_each(|idx| {
+            spawn_panel_node(
+                cmds,
+                panel,
+                content,
+                (SignedText(idx), panel_text("", PICO8_PEACH)),
+            );
+        });
+    });
+}
+
+fn sign_system(
+    In(entity): In<Entity>,
+    btns: Query<&SignBtn>,
+    mut contracts: ResMut<Contracts>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    if btn.0 >= contracts.offers.len() {
+        return;
+    }
+
+    if contracts.signed.len() >= MAX_CONTRACTS {
+        notifications.push("Too many contracts", PICO8_ORANGE, None);
+        return;
+    }
+
+    let offer = contracts.offers.remove(btn.0);
+    contracts.signed.push(offer);
+}
+
+fn update_desk_panel_system(
+    mut offer_texts: Query<(&mut UIText, &OfferText), Without<SignedText>>,
+    mut signed_texts: Query<(&mut UIText, &SignedText), Without<OfferText>>,
+    mut sign_btns: Query<(&mut UITextButton, &SignBtn)>,
+    contracts: Res<Contracts>,
+    stock: Res<TotalStock>,
+) {
+    offer_texts.iter_mut().for_each(|(mut text, idx)| {
+        text.text = contracts
+            .offers
+            .get(idx.0)
+            .map_or_else(|| "Taken".to_string(), Contract::describe);
+    });
+
+    sign_btns.iter_mut().for_each(|(mut btn, idx)| {
+        btn.enabled = idx.0 < contracts.offers.len() && contracts.signed.len() < MAX_CONTRACTS;
+    });
+
+    signed_texts.iter_mut().for_each(|(mut text, idx)| {
+        text.text = contracts.signed.get(idx.0).map_or_else(String::new, |c| {
+            format!("{} (have {:.0})", c.describe(), stock.get(c.kind))
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::{
+        ecs::bevy_ecs::{schedule::Schedule, system::RunSystemOnce},
+        math::UVec2,
+    };
+
+    use super::*;
+    use crate::{
+        camera::CamShake,
+        economy::shop_system,
+        game::{BuildKind, Building},
+        rules::Rules,
+    };
+
+    fn contract() -> Contract {
+        Contract {
+            kind: ResourceKind::Iron,
+            amount: 50.0,
+            price: 2.0,
+            due_day: 3,
+        }
+    }
+
+    #[test]
+    fn full_delivery_has_no_penalty() {
+        let result = settle(&contract(), 80.0);
+        assert_eq!(
+            result,
+            Settlement {
+                delivered: 50.0,
+                payment: 100.0,
+                penalty: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn partial_delivery_pays_the_missing_units() {
+        let result = settle(&contract(), 20.7);
+        assert_eq!(result.delivered, 20.0);
+        assert_eq!(result.payment, 40.0);
+        assert_eq!(result.penalty, 30.0 * 2.0 * CONTRACT_PENALTY_FACTOR);
+    }
+
+    #[test]
+    fn zero_delivery_pays_the_whole_penalty() {
+        let result = settle(&contract(), -5.0);
+        assert_eq!(result.delivered, 0.0);
+        assert_eq!(result.payment, 0.0);
+        assert_eq!(result.penalty, contract().value() * CONTRACT_PENALTY_FACTOR);
+    }
+
+    #[test]
+    fn same_seed_and_day_give_the_same_offers() {
+        let offers = roll_offers(0x1D57_EC0D_DEE9, 4, 1.0);
+        assert_eq!(offers.len(), CONTRACT_OFFERS);
+        assert_eq!(offers, roll_offers(0x1D57_EC0D_DEE9, 4, 1.0));
+        assert_ne!(offers, roll_offers(0x1D57_EC0D_DEE9, 5, 1.0));
+        assert_ne!(offers, roll_offers(0x5EED, 4, 1.0));
+
+        offers.iter().for_each(|offer| {
+            assert!(offer.kind.is_tradable());
+            assert!(offer.price >= offer.kind.base_price() * 1.1 - 0.01);
+            assert!(offer.price <= offer.kind.base_price() * 1.4 + 0.01);
+            let days = offer.due_day - 4;
+            assert!((CONTRACT_MIN_DAYS..=CONTRACT_MAX_DAYS).contains(&days));
+        });
+    }
+
+    #[test]
+    fn offers_follow_the_price_index() {
+        let base = roll_offers(42, 0, 1.0);
+        let inflated = roll_offers(42, 0, 2.0);
+        base.iter().zip(&inflated).for_each(|(a, b)| {
+            assert_eq!(a.kind, b.kind);
+            assert_eq!(a.amount, b.amount);
+            assert!((b.price - a.price * 2.0).abs() <= 0.01);
+        });
+    }
+
+    #[test]
+    fn draw_down_takes_from_the_first_stocks() {
+        assert_eq!(draw_down(&[30.0, 0.0, 40.0], 50.0), vec![30.0, 0.0, 20.0]);
+        assert_eq!(draw_down(&[30.0, 10.0], 80.0), vec![30.0, 10.0]);
+        assert_eq!(draw_down(&[30.0, -5.0, 10.0], 35.0), vec![30.0, 0.0, 5.0]);
+        assert_eq!(draw_down(&[30.0], 0.0), vec![0.0]);
+    }
+
+    /// World with the contracts signed and lands holding the amounts of the
+    /// resource, only the owned ones deliver
+    fn world_with_lands(
+        signed: Vec<Contract>,
+        kind: ResourceKind,
+        lands: &[(bool, f32)],
+    ) -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        world.insert_resource(Money(0.0));
+        world.insert_resource(Ledger::default());
+        world.insert_resource(Notifications::default());
+        world.insert_resource(Calendar::default());
+        world.insert_resource(Market::default());
+        world.insert_resource(GameSeed {
+            value: 1,
+            daily: false,
+        });
+        world.insert_resource(Contracts {
+            signed,
+            ..Default::default()
+        });
+
+        let lands = lands
+            .iter()
+            .map(|(owned, amount)| {
+                let mut stock = Stock::default();
+                stock.add(kind, *amount);
+                let land = Land {
+                    owned: *owned,
+                    ..Default::default()
+                };
+                world.spawn((land, stock)).id()
+            })
+            .collect();
+        (world, lands)
+    }
+
+    fn stock_of(world: &World, land: Entity, kind: ResourceKind) -> f32 {
+        world.get::<Stock>(land).unwrap().get(kind)
+    }
+
+    #[test]
+    fn delivery_draws_down_the_owned_lands_in_order() {
+        let due = Contract {
+            due_day: 0,
+            ..contract()
+        };
+        let lands = [(true, 30.0), (false, 80.0), (true, 0.0), (true, 40.0)];
+        let (mut world, lands) =
+            world_with_lands(vec![due, contract()], ResourceKind::Iron, &lands);
+        world.run_system_once(contracts_system).unwrap();
+
+        let left = lands
+            .iter()
+            .map(|land| stock_of(&world, *land, ResourceKind::Iron))
+            .collect::<Vec<_>>();
+        assert_eq!(left, vec![0.0, 80.0, 0.0, 20.0]);
+        assert_eq!(world.resource::<Money>().0, 100.0);
+
+        // the contract due later is still signed
+        assert_eq!(world.resource::<Contracts>().signed, vec![contract()]);
+    }
+
+    #[test]
+    fn short_delivery_empties_the_lands_and_pays_the_penalty() {
+        let due = Contract {
+            due_day: 0,
+            ..contract()
+        };
+        let lands = [(true, 30.0), (true, 15.5)];
+        let (mut world, lands) = world_with_lands(vec![due], ResourceKind::Iron, &lands);
+        world.run_system_once(contracts_system).unwrap();
+
+        assert_eq!(stock_of(&world, lands[0], ResourceKind::Iron), 0.0);
+        assert_eq!(stock_of(&world, lands[1], ResourceKind::Iron), 0.5);
+        let penalty = 5.0 * 2.0 * CONTRACT_PENALTY_FACTOR;
+        assert_eq!(world.resource::<Money>().0, 90.0 - penalty);
+        assert!(world.resource::<Contracts>().signed.is_empty());
+    }
+
+    #[test]
+    fn shops_keep_the_units_reserved_for_the_contracts() {
+        let copper = Contract {
+            kind: ResourceKind::Copper,
+            amount: 45.0,
+            price: 1.5,
+            due_day: 5,
+        };
+        let lands = [(true, 30.0), (true, 30.0)];
+        let (mut world, lands) = world_with_lands(vec![copper], ResourceKind::Copper, &lands);
+        world.insert_resource(CamShake::default());
+        world.insert_resource(Rules::default());
+        lands.iter().for_each(|land| {
+            world.spawn((Building::new(*land, UVec2::new(1, 1)), BuildKind::Shop));
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((reserve_system, shop_system).chain());
+        (0..5).for_each(|_| schedule.run(&mut world));
+
+        // the first land is reserved whole, the second sells what is left over
+        assert_eq!(stock_of(&world, lands[0], ResourceKind::Copper), 30.0);
+        assert_eq!(stock_of(&world, lands[1], ResourceKind::Copper), 15.0);
+        assert!(world.resource::<Money>().0 > 0.0);
+    }
+}
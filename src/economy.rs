@@ -0,0 +1,484 @@
+use rkit::{ecs::bevy_ecs::schedule::SystemConfigs, prelude::*};
+use rustc_hash::FxHashMap;
+use strum::IntoEnumIterator;
+
+use crate::{
+    calendar::Calendar,
+    camera::CamShake,
+    consts::*,
+    game::{BuildKind, Building, Land, ResourceKind, Shutdown},
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    prestige::Prestige,
+    rules::Rules,
+    screens::AppScreen,
+    society::Society,
+    terrain::Terrain,
+};
+
+pub fn economy_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_economy_system)
+        .add_screen_systems(screen, OnUpdate, tick_sim_clock_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Produce.on_tick((storage_system, production_system).chain()),
+        )
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Trade.on_tick((shop_system, market_recover_system, total_stock_system).chain()),
+        );
+}
+
+// - simulation clock
+#[derive(Resource, Default)]
+pub struct SimClock {
+    elapsed: f32,
+    ticked: bool,
+    pub tick: u64,
+}
+
+pub fn on_sim_tick(clock: Res<SimClock>) -> bool {
+    clock.ticked
+}
+
+/// Steps of a simulation tick, in the order they run after the clock
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SimSet {
+    /// Weather, digging, hazards and pollution change the lands
+    World,
+    /// Storage and production of the buildings
+    Produce,
+    /// Automation rules and contract deliveries, before the shops sell
+    Automate,
+    /// Shops, logistics, rivals and the market
+    Trade,
+    /// Upkeep, loans, taxes and inflation read the results of the trades
+    Settle,
+    /// Stats and the ledger once everything is booked
+    Record,
+}
+
+impl SimSet {
+    /// Runs the systems on the ticks in this step
+    pub fn on_tick<M>(self, systems: impl IntoSystemConfigs<M>) -> SystemConfigs {
+        let systems = systems
+            .run_if(on_sim_tick)
+            .in_set(self)
+            .after(tick_sim_clock_system);
+        match self.previous() {
+            Some(previous) => systems.after(previous),
+            None => systems,
+        }
+    }
+
+    fn previous(self) -> Option<SimSet> {
+        match self {
+            SimSet::World => None,
+            SimSet::Produce => Some(SimSet::World),
+            SimSet::Automate => Some(SimSet::Produce),
+            SimSet::Trade => Some(SimSet::Automate),
+            SimSet::Settle => Some(SimSet::Trade),
+            SimSet::Record => Some(SimSet::Settle),
+        }
+    }
+}
+
+// - stock
+/// Stockpile of a land, production and shops only use the one of their land
+#[derive(Component, Default)]
+pub struct Stock {
+    amounts: FxHashMap<ResourceKind, f32>,
+    /// Held back for the signed contracts, shops and rules do not sell it
+    reserved: FxHashMap<ResourceKind, f32>,
+    storage_level: u32,
+}
+
+impl Stock {
+    #[inline]
+    pub fn get(&self, kind: ResourceKind) -> f32 {
+        self.amounts.get(&kind).copied().unwrap_or(0.0)
+    }
+
+    /// Amount that can be traded, what is not reserved
+    #[inline]
+    pub fn free(&self, kind: ResourceKind) -> f32 {
+        let reserved = self.reserved.get(&kind).copied().unwrap_or(0.0);
+        (self.get(kind) - reserved).max(0.0)
+    }
+
+    pub fn reserve(&mut self, kind: ResourceKind, amount: f32) {
+        *self.reserved.entry(kind).or_default() += amount;
+    }
+
+    pub fn clear_reserved(&mut self) {
+        self.reserved.clear();
+    }
+
+    #[inline]
+    pub fn capacity(&self, kind: ResourceKind) -> f32 {
+        let bonus = self.storage_level as f32 * WAREHOUSE_CAPACITY_BONUS;
+        kind.base_capacity() * (1.0 + bonus)
+    }
+
+    /// Add the amount up to the storage capacity, returns the amount wasted
+    pub fn add(&mut self, kind: ResourceKind, amount: f32) -> f32 {
+        let current = self.get(kind);
+        let total = current + amount;
+        let stored = total.min(self.capacity(kind).max(current));
+        self.amounts.insert(kind, stored);
+        total - stored
+    }
+
+    /// Remove the amount only if there is enough of it
+    pub fn take(&mut self, kind: ResourceKind, amount: f32) -> bool {
+        let current = self.get(kind);
+        if current < amount {
+            return false;
+        }
+
+        self.amounts.insert(kind, current - amount);
+        true
+    }
+}
+
+/// Sum of the stockpiles of every owned land
+#[derive(Resource, Default)]
+pub struct TotalStock {
+    amounts: FxHashMap<ResourceKind, f32>,
+    capacities: FxHashMap<ResourceKind, f32>,
+}
+
+impl TotalStock {
+    #[inline]
+    pub fn get(&self, kind: ResourceKind) -> f32 {
+        self.amounts.get(&kind).copied().unwrap_or(0.0)
+    }
+
+    #[inline]
+    pub fn capacity(&self, kind: ResourceKind) -> f32 {
+        self.capacities.get(&kind).copied().unwrap_or(0.0)
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct Money(pub f32);
+
+// - market
+#[derive(Resource)]
+pub struct Market {
+    prices: FxHashMap<ResourceKind, f32>,
+    /// Inflation index applied to the base prices
+    pub index: f32,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        let prices = ResourceKind::iter()
+            .map(|kind| (kind, kind.base_price()))
+            .collect();
+        Self { prices, index: 1.0 }
+    }
+}
+
+impl Market {
+    #[inline]
+    pub fn price(&self, kind: ResourceKind) -> f32 {
+        self.prices.get(&kind).copied().unwrap_or(0.0)
+    }
+
+    /// Price that the market tends to without trading
+    #[inline]
+    pub fn base_price(&self, kind: ResourceKind) -> f32 {
+        kind.base_price() * self.index
+    }
+
+    /// Price once the amount is sold
+    pub fn price_after_sell(&self, kind: ResourceKind, amount: f32) -> f32 {
+        let min_price = self.base_price(kind) * MARKET_MIN_PRICE_FACTOR;
+        (self.price(kind) * (1.0 - MARKET_PRICE_IMPACT * amount)).max(min_price)
+    }
+
+    /// Price once the amount is bought
+    pub fn price_after_buy(&self, kind: ResourceKind, amount: f32) -> f32 {
+        let max_price = self.base_price(kind) * MARKET_MAX_PRICE_FACTOR;
+        (self.price(kind) * (1.0 + MARKET_PRICE_IMPACT * amount)).min(max_price)
+    }
+
+    /// Sell the amount, returns the money earned. Each sale pushes the price
+    /// down a bit and the units are paid at the average of the way down
+    pub fn sell(&mut self, kind: ResourceKind, amount: f32) -> f32 {
+        let price = self.price(kind);
+        let new_price = self.price_after_sell(kind, amount);
+        self.prices.insert(kind, new_price);
+        (price + new_price) * 0.5 * amount
+    }
+
+    /// Buy the amount, returns the money spent. Each purchase pushes the price
+    /// up a bit and the units are charged at the average of the way up
+    pub fn buy(&mut self, kind: ResourceKind, amount: f32) -> f32 {
+        let price = self.price(kind);
+        let new_price = self.price_after_buy(kind, amount);
+        self.prices.insert(kind, new_price);
+        (price + new_price) * 0.5 * amount
+    }
+
+    /// Move the prices back towards their base value
+    pub fn recover(&mut self) {
+        let index = self.index;
+        self.prices.iter_mut().for_each(|(kind, price)| {
+            let base = kind.base_price() * index;
+            *price += (base - *price) * MARKET_RECOVER_RATE;
+        });
+    }
+}
+
+impl ResourceKind {
+    /// Storage available without any warehouse
+    pub fn base_capacity(&self) -> f32 {
+        match self {
+            ResourceKind::Copper => 200.0,
+            ResourceKind::Iron => 150.0,
+            ResourceKind::Silver => 100.0,
+            ResourceKind::Gold => 60.0,
+            ResourceKind::Woord => 200.0,
+            ResourceKind::Food => 150.0,
+            ResourceKind::People => 50.0,
+            ResourceKind::Ring => 20.0,
+        }
+    }
+
+    pub fn base_price(&self) -> f32 {
+        match self {
+            ResourceKind::Copper => 1.0,
+            ResourceKind::Iron => 2.0,
+            ResourceKind::Silver => 4.0,
+            ResourceKind::Gold => 8.0,
+            ResourceKind::Woord => 1.0,
+            ResourceKind::Food => 0.5,
+            ResourceKind::People => 0.0,
+            ResourceKind::Ring => 25.0,
+        }
+    }
+
+    /// Resources that the shops can sell
+    pub fn is_tradable(&self) -> bool {
+        !matches!(self, ResourceKind::People | ResourceKind::Food)
+    }
+
+    /// Can be bought in the market by the automation rules
+    pub fn is_buyable(&self) -> bool {
+        !matches!(self, ResourceKind::People)
+    }
+}
+
+// - production
+pub struct Recipe {
+    pub input: Option<(ResourceKind, f32)>,
+    pub output: Option<(ResourceKind, f32)>,
+}
+
+impl BuildKind {
+    /// Base resources consumed and produced each simulation tick
+    pub fn recipe(&self, terrain: Terrain) -> Recipe {
+        let (input, output) = match self {
+            BuildKind::Farm => (None, Some((ResourceKind::Food, 1.0))),
+            BuildKind::House => (
+                Some((ResourceKind::Food, 0.5)),
+                Some((ResourceKind::People, 0.2)),
+            ),
+            BuildKind::Forest => (None, Some((ResourceKind::Woord, 1.0))),
+            BuildKind::Factory => (
+                Some((ResourceKind::Gold, 1.0)),
+                Some((ResourceKind::Ring, 0.5)),
+            ),
+            BuildKind::Shop => (None, None),
+            BuildKind::Mine => (None, Some((terrain.ore(), 1.0))),
+            BuildKind::Warehouse => (None, None),
+            BuildKind::Depot => (None, None),
+        };
+
+        Recipe { input, output }
+    }
+}
+
+// - systems
+fn init_economy_system(mut cmds: Commands, prestige: Res<Prestige>) {
+    cmds.insert_resource(SimClock::default());
+    cmds.insert_resource(TotalStock::default());
+    cmds.insert_resource(Money(prestige.starting_money()));
+    cmds.insert_resource(Market::default());
+}
+
+fn tick_sim_clock_system(mut clock: ResMut<SimClock>, time: Res<Time>) {
+    clock.elapsed += time.delta_f32();
+    clock.ticked = clock.elapsed >= SIM_TICK_TIME;
+    if clock.ticked {
+        clock.elapsed -= SIM_TICK_TIME;
+        clock.tick += 1;
+    }
+}
+
+/// Number of running shops on each land
+pub fn shops_per_land<'a>(
+    buildings: impl Iterator<Item = (&'a BuildKind, &'a Building)>,
+) -> FxHashMap<Entity, usize> {
+    let mut shops = FxHashMap::default();
+    buildings
+        .filter(|(kind, _)| matches!(kind, BuildKind::Shop))
+        .for_each(|(_, building)| *shops.entry(building.land).or_default() += 1);
+    shops
+}
+
+pub fn storage_system(
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+    mut stocks: Query<&mut Stock>,
+) {
+    stocks
+        .iter_mut()
+        .for_each(|mut stock| stock.storage_level = 0);
+
+    buildings
+        .iter()
+        .filter(|(kind, _)| matches!(kind, BuildKind::Warehouse))
+        .for_each(|(_, building)| {
+            if let Ok(mut stock) = stocks.get_mut(building.land) {
+                stock.storage_level += building.level;
+            }
+        });
+}
+
+pub fn production_system(
+    mut lands: Query<(Entity, &mut Land, &mut Stock)>,
+    mut buildings: Query<(&BuildKind, &mut Building), Without<Shutdown>>,
+    society: Res<Society>,
+    calendar: Res<Calendar>,
+    prestige: Res<Prestige>,
+    mut ledger: ResMut<Ledger>,
+    mut notifications: ResMut<Notifications>,
+) {
+    buildings.iter_mut().for_each(|(kind, mut building)| {
+        let Ok((land_e, mut land, mut stock)) = lands.get_mut(building.land) else {
+            return;
+        };
+
+        // storms and hazards on the tile stop the mine, nothing is produced
+        let hazard = land.excavation(building.pos).hazard.is_some();
+        if calendar.halts(*kind) || (*kind == BuildKind::Mine && hazard) {
+            return;
+        }
+
+        let level = building.level as f32;
+        let terrain = land.terrain(building.pos);
+        let recipe = kind.recipe(terrain);
+        let category = LedgerCategory::Building(*kind);
+        if let Some((res, amount)) = recipe.input {
+            if !stock.take(res, amount * level) {
+                return;
+            }
+
+            ledger.resource(category, res, -amount * level);
+        }
+
+        if let Some((res, amount)) = recipe.output {
+            // happy people grow faster and work better
+            let workers = match res {
+                ResourceKind::People => society.growth(),
+                _ => society.productivity(),
+            };
+
+            // anything that does not fit in the storage is lost
+            let pollution = land.pollution_multiplier(*kind, building.pos);
+            let season = calendar.multiplier(*kind);
+            let bonus = prestige.production_multiplier();
+            let amount =
+                amount * level * terrain.multiplier(*kind) * workers * pollution * season * bonus;
+
+            // mines on dug tiles extract from the deposit until it runs out,
+            // a dug tile without a deposit gives nothing
+            let (res, amount) = match kind {
+                BuildKind::Mine => {
+                    let (res, amount) = land.extract(building.pos, res, amount);
+                    let depleted = land
+                        .excavation(building.pos)
+                        .deposit
+                        .is_some_and(|d| d.is_depleted());
+                    if depleted && amount > 0.0 {
+                        notifications.push(
+                            "A deposit is depleted, dig deeper or abandon it",
+                            PICO8_ORANGE,
+                            Some(land_e),
+                        );
+                    }
+                    (res, amount)
+                }
+                _ => (res, amount),
+            };
+
+            let wasted = stock.add(res, amount);
+            ledger.resource(category, res, amount - wasted);
+            if !building.produced && amount > 0.0 {
+                building.produced = true;
+            }
+            if wasted > 0.0 {
+                log::debug!("{wasted:.2} of {res:?} wasted, storage is full");
+            }
+        }
+    });
+}
+
+pub fn shop_system(
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+    mut stocks: Query<&mut Stock>,
+    mut market: ResMut<Market>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+    mut shake: ResMut<CamShake>,
+    rules: Res<Rules>,
+) {
+    // shops only sell what is stored in their land
+    shops_per_land(buildings.iter())
+        .into_iter()
+        .for_each(|(land, shops)| {
+            let Ok(mut stock) = stocks.get_mut(land) else {
+                return;
+            };
+
+            let max_amount = shops as f32 * SHOP_SELL_RATE;
+            // the resources with a rule are only traded by the rule
+            ResourceKind::iter()
+                .filter(|kind| kind.is_tradable() && !rules.trades(*kind))
+                .for_each(|kind| {
+                    let amount = stock.free(kind).min(max_amount).floor();
+                    if amount <= 0.0 || !stock.take(kind, amount) {
+                        return;
+                    }
+
+                    let earned = market.sell(kind, amount);
+                    money.0 += earned;
+                    ledger.money(LedgerCategory::Sales, earned);
+                    ledger.resource(LedgerCategory::Sales, kind, -amount);
+                    shake.trade(earned);
+                });
+        });
+}
+
+pub fn market_recover_system(mut market: ResMut<Market>) {
+    market.recover();
+}
+
+pub fn total_stock_system(lands: Query<(&Land, &Stock)>, mut total: ResMut<TotalStock>) {
+    total.amounts.clear();
+    total.capacities.clear();
+    lands
+        .iter()
+        .filter(|(land, _)| land.owned)
+        .for_each(|(_, stock)| {
+            ResourceKind::iter().for_each(|kind| {
+                *total.amounts.entry(kind).or_default() += stock.get(kind);
+                *total.capacities.entry(kind).or_default() += stock.capacity(kind);
+            });
+        });
+}
@@ -0,0 +1,289 @@
+use rkit::{
+    math::{IVec2, UVec2},
+    prelude::*,
+};
+
+use crate::{
+    components::Pos,
+    consts::*,
+    economy::SimSet,
+    game::{Land, ResourceKind},
+    hazards::Hazard,
+    notify::Notifications,
+    screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+    terrain::Terrain,
+    worldgen::cell_at,
+};
+
+// Salt to keep the deposits independent of the terrain generation
+const DEPOSIT_SALT: u64 = 0xD1_6000;
+
+pub fn excavation_plugin(app: &mut App) {
+    app.add_screen_systems(AppScreen::Game, OnUpdate, SimSet::World.on_tick(dig_system));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deposit {
+    pub ore: ResourceKind,
+    pub size: f32,
+    pub remaining: f32,
+}
+
+impl Deposit {
+    pub fn is_depleted(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
+
+/// State of the digging on a tile
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Excavation {
+    /// Times the tile has been dug
+    pub depth: u32,
+    /// Revealed once the digging at the current depth is done, none if
+    /// there was nothing to find
+    pub deposit: Option<Deposit>,
+    /// Progress from 0 to 1 while digging
+    pub progress: Option<f32>,
+    pub abandoned: bool,
+    /// Stops the mine and the digging on the tile
+    pub hazard: Option<Hazard>,
+    pub pump: bool,
+    pub ventilation: bool,
+    pub supports: u32,
+}
+
+impl Excavation {
+    pub fn is_digging(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// A tile can be dug if it was never dug or if there is nothing left
+    pub fn can_dig(&self) -> bool {
+        !self.abandoned
+            && !self.is_digging()
+            && self.hazard.is_none()
+            && self.deposit.is_none_or(|deposit| deposit.is_depleted())
+    }
+
+    pub fn can_abandon(&self) -> bool {
+        !self.abandoned && self.depth > 0 && self.can_dig()
+    }
+
+    /// Deeper levels cost more and take longer
+    pub fn dig_cost(&self, index: f32) -> f32 {
+        DIG_COST * (self.depth + 1) as f32 * index
+    }
+
+    /// Resource and amount that a mine producing the output would take, the
+    /// surface gives the output as is and a dug tile only its deposit
+    pub fn extractable(&self, res: ResourceKind, amount: f32) -> (ResourceKind, f32) {
+        match self.deposit {
+            Some(deposit) => (deposit.ore, (amount * DEPOSIT_YIELD).min(deposit.remaining)),
+            None if self.depth > 0 => (res, 0.0),
+            None => (res, amount),
+        }
+    }
+
+    fn dig_ticks(&self) -> u32 {
+        DIG_TICKS * (self.depth + 1)
+    }
+}
+
+/// Deposit hidden on the tile at the depth, the same seed always gives the same
+pub fn generate_deposit(
+    seed: u64,
+    cell: IVec2,
+    tile: UVec2,
+    depth: u32,
+    terrain: Terrain,
+) -> Option<Deposit> {
+    // each input is hashed into the next so no two tiles share a deposit
+    let inputs = [
+        cell.x as u64,
+        cell.y as u64,
+        Land::tile_index(tile) as u64,
+        depth as u64,
+    ];
+    let hash = inputs.iter().fold(seed ^ DEPOSIT_SALT, |hash, &input| {
+        SeedRng::fork(hash, input).next_u64()
+    });
+    let mut rng = SeedRng::new(hash);
+    if !rng.chance(DEPOSIT_CHANCE) {
+        return None;
+    }
+
+    // veins keep their ore and deeper deposits are bigger
+    let ore = match terrain {
+        Terrain::Vein(ore) => ore,
+        _ => match rng.range(0, 100) {
+            0..45 => ResourceKind::Copper,
+            45..75 => ResourceKind::Iron,
+            75..92 => ResourceKind::Silver,
+            _ => ResourceKind::Gold,
+        },
+    };
+    let size = (rng.range(DEPOSIT_MIN_SIZE, DEPOSIT_MAX_SIZE) * depth) as f32;
+    Some(Deposit {
+        ore,
+        size,
+        remaining: size,
+    })
+}
+
+impl Land {
+    pub fn excavation(&self, tile: UVec2) -> Excavation {
+        self.excavations
+            .get(Self::tile_index(tile))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn excavation_mut(&mut self, tile: UVec2) -> Option<&mut Excavation> {
+        self.excavations.get_mut(Self::tile_index(tile))
+    }
+
+    /// Takes what a mine producing the output extracts from the tile, see
+    /// [`Excavation::extractable`]
+    pub fn extract(&mut self, tile: UVec2, res: ResourceKind, amount: f32) -> (ResourceKind, f32) {
+        let (res, amount) = self.excavation(tile).extractable(res, amount);
+        if let Some(deposit) = self
+            .excavation_mut(tile)
+            .and_then(|excavation| excavation.deposit.as_mut())
+        {
+            deposit.remaining -= amount;
+        }
+        (res, amount)
+    }
+}
+
+// - systems
+fn dig_system(
+    mut lands: Query<(Entity, &mut Land, &Pos)>,
+    seed: Res<GameSeed>,
+    mut notifications: ResMut<Notifications>,
+) {
+    lands.iter_mut().for_each(|(entity, mut land, pos)| {
+        let cell = cell_at(pos.0);
+        let terrain = land.terrain.clone();
+        land.excavations
+            .iter_mut()
+            .enumerate()
+            .for_each(|(idx, excavation)| {
+                let Some(progress) = excavation.progress else {
+                    return;
+                };
+
+                let progress = progress + 1.0 / excavation.dig_ticks() as f32;
+                if progress < 1.0 {
+                    excavation.progress = Some(progress);
+                    return;
+                }
+
+                let tile = UVec2::new(
+                    idx as u32 % LAND_SIZE.x as u32,
+                    idx as u32 / LAND_SIZE.x as u32,
+                );
+                excavation.progress = None;
+                excavation.depth += 1;
+                excavation.deposit = generate_deposit(
+                    seed.value,
+                    cell,
+                    tile,
+                    excavation.depth,
+                    terrain.get(idx).copied().unwrap_or_default(),
+                );
+
+                match excavation.deposit {
+                    Some(deposit) => notifications.push(
+                        format!("Found {:.0} {}", deposit.size, deposit.ore.name()),
+                        PICO8_GREEN,
+                        Some(entity),
+                    ),
+                    None => notifications.push(
+                        "Nothing found, dig deeper or abandon",
+                        PICO8_ORANGE,
+                        Some(entity),
+                    ),
+                }
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposits(cell: IVec2) -> Vec<Option<Deposit>> {
+        (1..=4)
+            .flat_map(|depth| {
+                (0..9).map(move |idx| {
+                    let tile = UVec2::new(idx % 3, idx / 3);
+                    generate_deposit(42, cell, tile, depth, Terrain::Plain)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_deposits() {
+        assert_eq!(deposits(IVec2::new(1, 2)), deposits(IVec2::new(1, 2)));
+    }
+
+    #[test]
+    fn far_cells_have_their_own_deposits() {
+        let cell = IVec2::new(1, 2);
+        assert_ne!(deposits(cell), deposits(cell + IVec2::new(256, 0)));
+        assert_ne!(deposits(cell), deposits(cell + IVec2::new(0, 256)));
+        assert_ne!(deposits(cell), deposits(IVec2::new(2, 1)));
+    }
+
+    #[test]
+    fn dug_tile_without_deposit_gives_nothing() {
+        let surface = Excavation::default();
+        assert_eq!(
+            surface.extractable(ResourceKind::Copper, 3.0),
+            (ResourceKind::Copper, 3.0)
+        );
+
+        let empty = Excavation {
+            depth: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            empty.extractable(ResourceKind::Copper, 3.0),
+            (ResourceKind::Copper, 0.0)
+        );
+    }
+
+    #[test]
+    fn deposit_gives_its_ore_until_it_runs_out() {
+        let mut excavation = Excavation {
+            depth: 2,
+            deposit: Some(Deposit {
+                ore: ResourceKind::Gold,
+                size: 100.0,
+                remaining: 5.0,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            excavation.extractable(ResourceKind::Copper, 2.0),
+            (ResourceKind::Gold, 2.0 * DEPOSIT_YIELD)
+        );
+        assert_eq!(
+            excavation.extractable(ResourceKind::Copper, 10.0),
+            (ResourceKind::Gold, 5.0)
+        );
+
+        excavation.deposit = excavation.deposit.map(|d| Deposit {
+            remaining: 0.0,
+            ..d
+        });
+        assert_eq!(
+            excavation.extractable(ResourceKind::Copper, 10.0),
+            (ResourceKind::Gold, 0.0)
+        );
+    }
+}
@@ -4,29 +4,94 @@ use rkit::{
     math::{Rect, UVec2, Vec2},
     prelude::*,
 };
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::{
+    bank::bank_plugin,
+    build::build_plugin,
+    calendar::calendar_plugin,
     camera::{Cam, GameCam},
     components::Pos,
     consts::*,
+    contracts::contracts_plugin,
+    economy::{Stock, economy_plugin},
+    excavation::{Excavation, excavation_plugin},
+    hazards::hazards_plugin,
+    inflation::inflation_plugin,
+    input::Pointer,
+    ledger::ledger_plugin,
+    logistics::logistics_plugin,
+    minimap::minimap_plugin,
+    notify::notify_plugin,
+    pollution::pollution_plugin,
+    prestige::prestige_plugin,
+    rivals::rivals_plugin,
+    rules::rules_plugin,
     screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+    society::society_plugin,
+    stats::stats_plugin,
+    terrain::Terrain,
+    undo::undo_plugin,
+    upkeep::upkeep_plugin,
+    worldgen::generate_world,
 };
 
 pub fn game_plugin(app: &mut App) {
     let screen = AppScreen::Game;
-    app.add_systems(OnEnter(screen), init_game_resources_system)
+    app.add_resource(GameSeed::from_args_or_random())
+        .add_plugin(economy_plugin)
+        .add_plugin(build_plugin)
+        .add_plugin(upkeep_plugin)
+        .add_plugin(notify_plugin)
+        .add_plugin(bank_plugin)
+        .add_plugin(society_plugin)
+        .add_plugin(pollution_plugin)
+        .add_plugin(calendar_plugin)
+        .add_plugin(ledger_plugin)
+        .add_plugin(stats_plugin)
+        .add_plugin(prestige_plugin)
+        .add_plugin(undo_plugin)
+        .add_plugin(rules_plugin)
+        .add_plugin(logistics_plugin)
+        .add_plugin(rivals_plugin)
+        .add_plugin(contracts_plugin)
+        .add_plugin(inflation_plugin)
+        .add_plugin(excavation_plugin)
+        .add_plugin(hazards_plugin)
+        .add_plugin(minimap_plugin)
+        .add_systems(OnEnter(screen), init_game_resources_system)
+        .add_systems(OnExit(screen), cleanup_game_system)
         .add_screen_systems(screen, OnUpdate, find_focus_system)
         .add_screen_systems(screen, OnPostUpdate, on_added_building_system);
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, Debug)]
 pub struct Building {
     pub land: Entity,
     pub pos: UVec2,
+    pub level: u32,
+    /// Has produced something since it was built or upgraded
+    pub produced: bool,
+}
+
+/// Building stopped because there is no money to pay its upkeep
+#[derive(Component, Clone, Copy)]
+pub struct Shutdown;
+
+impl Building {
+    pub fn new(land: Entity, pos: UVec2) -> Self {
+        Self {
+            land,
+            pos,
+            level: 1,
+            produced: false,
+        }
+    }
 }
 
-#[derive(Component, Clone, Copy, EnumIter)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
 pub enum BuildKind {
     Farm,
     House,
@@ -34,9 +99,42 @@ pub enum BuildKind {
     Factory,
     Shop,
     Mine,
+    Warehouse,
+    Depot,
 }
 
-#[derive(Component, Clone, Copy, EnumIter)]
+impl BuildKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildKind::Farm => "Farm",
+            BuildKind::House => "House",
+            BuildKind::Forest => "Forest",
+            BuildKind::Factory => "Factory",
+            BuildKind::Shop => "Shop",
+            BuildKind::Mine => "Mine",
+            BuildKind::Warehouse => "Warehouse",
+            BuildKind::Depot => "Depot",
+        }
+    }
+
+    /// Money needed to build it with the current inflation index,
+    /// upgrading costs the same times the current level
+    pub fn cost(&self, index: f32) -> f32 {
+        let base = match self {
+            BuildKind::Farm => 20.0,
+            BuildKind::House => 30.0,
+            BuildKind::Forest => 15.0,
+            BuildKind::Factory => 120.0,
+            BuildKind::Shop => 60.0,
+            BuildKind::Mine => 50.0,
+            BuildKind::Warehouse => 80.0,
+            BuildKind::Depot => 70.0,
+        };
+        base * index
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter)]
 pub enum ResourceKind {
     Copper,
     Iron,
@@ -48,19 +146,87 @@ pub enum ResourceKind {
     Ring,
 }
 
+impl ResourceKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceKind::Copper => "Copper",
+            ResourceKind::Iron => "Iron",
+            ResourceKind::Silver => "Silver",
+            ResourceKind::Gold => "Gold",
+            ResourceKind::Woord => "Wood",
+            ResourceKind::Food => "Food",
+            ResourceKind::People => "People",
+            ResourceKind::Ring => "Ring",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ResourceKind::iter().find(|kind| kind.name() == name)
+    }
+}
+
 #[derive(Component, Default)]
 pub struct Land {
     pub buildings: HashSet<Entity>,
     pub hover: Option<UVec2>,
     pub focus: Option<UVec2>,
+    pub terrain: Vec<Terrain>,
+    pub pollution: Vec<f32>,
+    pub excavations: Vec<Excavation>,
+    pub owned: bool,
+    /// Rival company that bought the land
+    pub rival: Option<Entity>,
+    /// Buildings of the rival, only the rival runs them so they are not entities
+    pub rival_buildings: Vec<(UVec2, BuildKind)>,
+    pub price: f32,
 }
 
 impl Land {
+    pub fn new(rng: &mut SeedRng) -> Self {
+        let tiles = LAND_SIZE.x as usize * LAND_SIZE.y as usize;
+        let terrain = (0..tiles).map(|_| Terrain::generate(rng)).collect();
+        Self {
+            terrain,
+            pollution: vec![0.0; tiles],
+            excavations: vec![Excavation::default(); tiles],
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn tile_index(tile: UVec2) -> usize {
+        (tile.y * LAND_SIZE.x as u32 + tile.x) as usize
+    }
+
+    pub fn terrain(&self, tile: UVec2) -> Terrain {
+        self.terrain
+            .get(Self::tile_index(tile))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn pollution(&self, tile: UVec2) -> f32 {
+        self.pollution
+            .get(Self::tile_index(tile))
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub fn bounds(&self, pos: Vec2) -> Rect {
         let size = LAND_SIZE * (TILE_SIZE + TILE_GAP);
         Rect::new(pos - size * 0.5, size)
     }
 
+    pub fn tile_at(&self, pos: Vec2, point: Vec2) -> Option<UVec2> {
+        let bounds = self.bounds(pos);
+        if !bounds.contains(point) {
+            return None;
+        }
+
+        let relative_pos = point - bounds.min();
+        Some((relative_pos / (TILE_SIZE + TILE_GAP)).as_uvec2())
+    }
+
     pub fn add(&mut self, building: Entity) {
         self.buildings.insert(building);
     }
@@ -70,15 +236,24 @@ impl Land {
     }
 }
 
-fn init_game_resources_system(mut cmds: Commands) {
-    let land_e = cmds.spawn((Pos(RESOLUTION * 0.5), Land::default())).id();
-    cmds.spawn((
-        Building {
-            land: land_e,
-            pos: UVec2::new(1, 1),
-        },
-        BuildKind::Mine,
-    ));
+fn init_game_resources_system(mut cmds: Commands, seed: Res<GameSeed>) {
+    log::info!("Generating world with seed {}", seed.label());
+    generate_world(seed.value).into_iter().for_each(|plot| {
+        let owned = plot.land.owned;
+        let land_e = cmds
+            .spawn((Pos(plot.pos), plot.land, Stock::default()))
+            .id();
+        if !owned {
+            return;
+        }
+
+        cmds.spawn((Building::new(land_e, UVec2::new(1, 1)), BuildKind::Mine));
+        cmds.spawn((Building::new(land_e, UVec2::new(2, 1)), BuildKind::Shop));
+    });
+}
+
+fn cleanup_game_system(mut cmds: Commands, query: Query<Entity, Or<(With<Land>, With<Building>)>>) {
+    query.iter().for_each(|e| cmds.entity(e).despawn());
 }
 
 fn on_added_building_system(
@@ -95,26 +270,38 @@ fn on_added_building_system(
 }
 
 fn find_focus_system(
-    mut lands: Query<(&mut Land, &Pos)>,
-    mouse: Res<Mouse>,
+    mut lands: Query<(Entity, &mut Land, &Pos)>,
+    pointer: Res<Pointer>,
     cam: Single<&Cam, With<GameCam>>,
 ) {
-    let local_pos = cam.screen_to_local(mouse.position());
-    lands.iter_mut().for_each(|(mut land, pos)| {
-        let bounds = land.bounds(pos.0);
-        let mouse_hover = bounds.contains(local_pos);
-        if !mouse_hover {
-            land.hover = None;
-            return;
-        }
+    // right click or long press clears the focus
+    if pointer.secondary {
+        lands
+            .iter_mut()
+            .for_each(|(_, mut land, _)| land.focus = None);
+        return;
+    }
 
+    let local_pos = cam.screen_to_local(pointer.position);
+    let mut focused = None;
+    lands.iter_mut().for_each(|(entity, mut land, pos)| {
         // set hover
-        let relative_pos = local_pos - (bounds.min());
-        let tile = (relative_pos / (TILE_SIZE + TILE_GAP)).as_uvec2();
-        land.hover = Some(tile);
+        land.hover = land.tile_at(pos.0, local_pos);
+        let Some(tile) = land.hover else {
+            return;
+        };
 
-        if mouse.just_pressed(MouseButton::Left) {
+        if pointer.click {
             land.focus = Some(tile);
+            focused = Some(entity);
         }
     });
+
+    // only one land can have the focus at the same time
+    if let Some(focused) = focused {
+        lands
+            .iter_mut()
+            .filter(|(entity, ..)| *entity != focused)
+            .for_each(|(_, mut land, _)| land.focus = None);
+    }
 }
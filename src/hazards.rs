@@ -0,0 +1,382 @@
+use rkit::{gfx::Color, prelude::*};
+
+use crate::{
+    camera::CamShake,
+    consts::*,
+    economy::{SimClock, SimSet},
+    excavation::Excavation,
+    game::Land,
+    notify::Notifications,
+    screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+};
+
+// Salt to roll the hazards of each day
+const HAZARD_SALT: u64 = 0x4A_2A4D;
+
+pub fn hazards_plugin(app: &mut App) {
+    app.add_resource(HazardConfig::default())
+        .add_screen_systems(
+            AppScreen::Game,
+            OnUpdate,
+            SimSet::World.on_tick(hazards_system),
+        );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hazard {
+    /// Groundwater, stays until a pump is built
+    Flood,
+    /// Stays until the tile has ventilation
+    Gas,
+    /// Blocks the tile for the remaining ticks
+    CaveIn(u32),
+}
+
+impl Hazard {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hazard::Flood => "Flooding",
+            Hazard::Gas => "Gas pocket",
+            Hazard::CaveIn(_) => "Cave-in",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Hazard::Flood => PICO8_BLUE,
+            Hazard::Gas => PICO8_ORANGE,
+            Hazard::CaveIn(_) => PICO8_RED,
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            Hazard::Flood => "build a pump",
+            Hazard::Gas => "build ventilation",
+            Hazard::CaveIn(_) => "upgrade the supports",
+        }
+    }
+}
+
+/// Daily chance of a hazard at a depth, zero before the min depth
+#[derive(Clone, Copy, Debug)]
+pub struct RiskCurve {
+    pub min_depth: u32,
+    pub base: f32,
+    pub per_depth: f32,
+    pub max: f32,
+}
+
+impl RiskCurve {
+    pub fn chance(&self, depth: u32) -> f32 {
+        if depth < self.min_depth {
+            return 0.0;
+        }
+
+        let levels = (depth - self.min_depth) as f32;
+        (self.base + self.per_depth * levels).min(self.max)
+    }
+}
+
+/// Tuning of the hazards, all the risks are balanced from here
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HazardConfig {
+    pub flood: RiskCurve,
+    pub gas: RiskCurve,
+    pub cave_in: RiskCurve,
+    /// Multiplies the cave-in risk for each level of supports
+    pub supports_factor: f32,
+    /// Ticks that a cave-in blocks the tile
+    pub cave_in_ticks: u32,
+}
+
+impl Default for HazardConfig {
+    fn default() -> Self {
+        Self {
+            flood: RiskCurve {
+                min_depth: FLOOD_MIN_DEPTH,
+                base: FLOOD_BASE_RISK,
+                per_depth: FLOOD_RISK_PER_DEPTH,
+                max: HAZARD_MAX_RISK,
+            },
+            gas: RiskCurve {
+                min_depth: GAS_MIN_DEPTH,
+                base: GAS_BASE_RISK,
+                per_depth: GAS_RISK_PER_DEPTH,
+                max: HAZARD_MAX_RISK,
+            },
+            cave_in: RiskCurve {
+                min_depth: CAVE_IN_MIN_DEPTH,
+                base: CAVE_IN_BASE_RISK,
+                per_depth: CAVE_IN_RISK_PER_DEPTH,
+                max: HAZARD_MAX_RISK,
+            },
+            supports_factor: SUPPORTS_RISK_FACTOR,
+            cave_in_ticks: CAVE_IN_TICKS,
+        }
+    }
+}
+
+/// Countermeasures that can be installed on a dug tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Safety {
+    Pump,
+    Ventilation,
+    Supports,
+}
+
+impl Excavation {
+    pub fn can_secure(&self, safety: Safety) -> bool {
+        if self.abandoned || self.depth == 0 {
+            return false;
+        }
+
+        match safety {
+            Safety::Pump => !self.pump,
+            Safety::Ventilation => !self.ventilation,
+            Safety::Supports => self.supports < SUPPORTS_MAX_LEVEL,
+        }
+    }
+
+    /// Each level of supports costs more than the previous one
+    pub fn secure_cost(&self, safety: Safety, index: f32) -> f32 {
+        let cost = match safety {
+            Safety::Pump => PUMP_COST,
+            Safety::Ventilation => VENTILATION_COST,
+            Safety::Supports => SUPPORTS_COST * (self.supports + 1) as f32,
+        };
+        cost * index
+    }
+
+    /// Installs the safety, a pump drains the water and ventilation clears the gas
+    pub fn secure(&mut self, safety: Safety) {
+        match safety {
+            Safety::Pump => self.pump = true,
+            Safety::Ventilation => self.ventilation = true,
+            Safety::Supports => self.supports += 1,
+        }
+
+        let cleared = matches!(
+            (safety, self.hazard),
+            (Safety::Pump, Some(Hazard::Flood)) | (Safety::Ventilation, Some(Hazard::Gas))
+        );
+        if cleared {
+            self.hazard = None;
+        }
+    }
+
+    /// Daily chance of a flood, gas and cave-in, the safety installed avoids them
+    fn risks(&self, config: &HazardConfig) -> [f32; 3] {
+        let flood = if self.pump {
+            0.0
+        } else {
+            config.flood.chance(self.depth)
+        };
+        let gas = if self.ventilation {
+            0.0
+        } else {
+            config.gas.chance(self.depth)
+        };
+        let cave_in =
+            config.cave_in.chance(self.depth) * config.supports_factor.powi(self.supports as _);
+        [flood, gas, cave_in]
+    }
+
+    /// Rolls a new hazard for the tile
+    fn roll_hazard(&self, config: &HazardConfig, rng: &mut SeedRng) -> Option<Hazard> {
+        // every tile consumes the same numbers to keep the sequence stable
+        let [flood, gas, cave_in] = self.risks(config);
+        let rolls = [rng.chance(flood), rng.chance(gas), rng.chance(cave_in)];
+        match rolls {
+            [true, ..] => Some(Hazard::Flood),
+            [_, true, _] => Some(Hazard::Gas),
+            [.., true] => Some(Hazard::CaveIn(config.cave_in_ticks)),
+            _ => None,
+        }
+    }
+}
+
+// - systems
+fn hazards_system(
+    mut lands: Query<(Entity, &mut Land)>,
+    clock: Res<SimClock>,
+    seed: Res<GameSeed>,
+    config: Res<HazardConfig>,
+    mut notifications: ResMut<Notifications>,
+    mut shake: ResMut<CamShake>,
+) {
+    // the cave-ins are cleared after a while
+    lands.iter_mut().for_each(|(_, mut land)| {
+        land.excavations
+            .iter_mut()
+            .for_each(|excavation| match excavation.hazard {
+                Some(Hazard::CaveIn(ticks)) if ticks <= 1 => excavation.hazard = None,
+                Some(Hazard::CaveIn(ticks)) => excavation.hazard = Some(Hazard::CaveIn(ticks - 1)),
+                _ => {}
+            });
+    });
+
+    let new_day = clock.tick % DAY_TICKS as u64 == 0;
+    if !new_day {
+        return;
+    }
+
+    let mut rng = SeedRng::fork(seed.value ^ HAZARD_SALT, clock.tick);
+    let mut list = lands
+        .iter_mut()
+        .filter(|(_, land)| land.owned)
+        .collect::<Vec<_>>();
+    list.sort_by_key(|(e, _)| *e);
+    list.into_iter().for_each(|(entity, mut land)| {
+        land.excavations
+            .iter_mut()
+            .filter(|excavation| {
+                excavation.depth > 0
+                    && !excavation.abandoned
+                    && !excavation.is_digging()
+                    && excavation.hazard.is_none()
+            })
+            .for_each(|excavation| {
+                let Some(hazard) = excavation.roll_hazard(&config, &mut rng) else {
+                    return;
+                };
+
+                excavation.hazard = Some(hazard);
+                if matches!(hazard, Hazard::CaveIn(_)) {
+                    shake.add_trauma(SHAKE_CAVE_IN_TRAUMA);
+                }
+                notifications.push(
+                    format!(
+                        "{} at depth {}, {}",
+                        hazard.name(),
+                        excavation.depth,
+                        hazard.hint()
+                    ),
+                    hazard.color(),
+                    Some(entity),
+                );
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dug(depth: u32) -> Excavation {
+        Excavation {
+            depth,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn risk_starts_at_the_min_depth_and_is_capped() {
+        let curve = RiskCurve {
+            min_depth: 2,
+            base: 0.1,
+            per_depth: 0.2,
+            max: 0.6,
+        };
+        assert_eq!(curve.chance(0), 0.0);
+        assert_eq!(curve.chance(1), 0.0);
+        assert_eq!(curve.chance(2), 0.1);
+        assert!((curve.chance(3) - 0.3).abs() < 1e-6);
+        assert!((curve.chance(4) - 0.5).abs() < 1e-6);
+        assert_eq!(curve.chance(5), 0.6);
+        assert_eq!(curve.chance(100), 0.6);
+    }
+
+    #[test]
+    fn safety_clears_its_own_hazard_only() {
+        let mut flooded = Excavation {
+            hazard: Some(Hazard::Flood),
+            ..dug(3)
+        };
+        flooded.secure(Safety::Ventilation);
+        assert_eq!(flooded.hazard, Some(Hazard::Flood));
+        flooded.secure(Safety::Pump);
+        assert_eq!(flooded.hazard, None);
+        assert!(flooded.pump && flooded.ventilation);
+
+        let mut gas = Excavation {
+            hazard: Some(Hazard::Gas),
+            ..dug(3)
+        };
+        gas.secure(Safety::Pump);
+        assert_eq!(gas.hazard, Some(Hazard::Gas));
+        gas.secure(Safety::Ventilation);
+        assert_eq!(gas.hazard, None);
+
+        // the supports only lower the risk, the cave-in stays
+        let mut caved = Excavation {
+            hazard: Some(Hazard::CaveIn(10)),
+            ..dug(3)
+        };
+        caved.secure(Safety::Supports);
+        assert_eq!(caved.hazard, Some(Hazard::CaveIn(10)));
+        assert_eq!(caved.supports, 1);
+    }
+
+    #[test]
+    fn safety_removes_or_scales_the_risks() {
+        let config = HazardConfig::default();
+        let [flood, gas, cave_in] = dug(6).risks(&config);
+        assert!(flood > 0.0 && gas > 0.0 && cave_in > 0.0);
+
+        let secured = Excavation {
+            pump: true,
+            ventilation: true,
+            supports: 2,
+            ..dug(6)
+        };
+        let [s_flood, s_gas, s_cave_in] = secured.risks(&config);
+        assert_eq!(s_flood, 0.0);
+        assert_eq!(s_gas, 0.0);
+        let expected = cave_in * config.supports_factor * config.supports_factor;
+        assert!((s_cave_in - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn same_seed_rolls_the_same_hazards() {
+        let config = HazardConfig::default();
+        let tiles = (1..=12).map(dug).collect::<Vec<_>>();
+        let roll = |seed: u64| {
+            let mut rng = SeedRng::fork(seed, HAZARD_SALT);
+            tiles
+                .iter()
+                .map(|tile| tile.roll_hazard(&config, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(roll(7), roll(7));
+        assert_ne!(roll(7), roll(8));
+    }
+
+    #[test]
+    fn certain_risks_roll_in_order() {
+        let certain = RiskCurve {
+            min_depth: 1,
+            base: 1.0,
+            per_depth: 0.0,
+            max: 1.0,
+        };
+        let config = HazardConfig {
+            flood: certain,
+            gas: certain,
+            cave_in: certain,
+            ..Default::default()
+        };
+        let mut rng = SeedRng::new(1);
+        let mut tile = dug(1);
+        assert_eq!(tile.roll_hazard(&config, &mut rng), Some(Hazard::Flood));
+        tile.pump = true;
+        assert_eq!(tile.roll_hazard(&config, &mut rng), Some(Hazard::Gas));
+        tile.ventilation = true;
+        assert_eq!(
+            tile.roll_hazard(&config, &mut rng),
+            Some(Hazard::CaveIn(config.cave_in_ticks))
+        );
+        assert_eq!(dug(0).roll_hazard(&config, &mut rng), None);
+    }
+}
@@ -0,0 +1,205 @@
+use rkit::prelude::*;
+
+use crate::{
+    consts::*,
+    economy::{Market, SimSet},
+    ledger::{Ledger, LedgerMinute},
+    screens::AppScreen,
+};
+
+pub fn inflation_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_inflation_system)
+        .add_screen_systems(screen, OnUpdate, SimSet::Settle.on_tick(inflation_system))
+        .add_screen_systems(screen, OnUpdate, update_inflation_text_system);
+}
+
+#[derive(Resource)]
+pub struct Inflation {
+    /// Multiplier of the prices and building costs
+    pub index: f32,
+    /// Change of the index per day
+    pub rate: f32,
+}
+
+impl Default for Inflation {
+    fn default() -> Self {
+        Self {
+            index: 1.0,
+            rate: 0.0,
+        }
+    }
+}
+
+impl Inflation {
+    pub fn label(&self) -> String {
+        format!("Inflation {:+.1}%/day", self.rate * 100.0)
+    }
+
+    /// Moves a tick forward with the money printed and sunk during the tick
+    fn tick(&mut self, printed: f32, sunk: f32) {
+        // flow per tick scaled to a day
+        let day = DAY_TICKS as f32;
+        self.rate = next_rate(self.rate, printed * day, sunk * day);
+        self.index = next_index(self.index, self.rate);
+    }
+}
+
+/// Money created (income) and destroyed (expenses) in the deltas
+fn money_flow(minute: &LedgerMinute) -> (f32, f32) {
+    (minute.income(), -minute.expenses())
+}
+
+/// Rate moves towards the pressure of the new money and is kept in bounds
+pub fn next_rate(rate: f32, printed: f32, sunk: f32) -> f32 {
+    let target = (printed - sunk) / INFLATION_MONEY_SCALE;
+    let rate = rate + (target - rate) * INFLATION_ADJUST;
+    rate.clamp(INFLATION_MIN_RATE, INFLATION_MAX_RATE)
+}
+
+/// Index after one tick at the rate given
+pub fn next_index(index: f32, rate: f32) -> f32 {
+    let index = index * (1.0 + rate / DAY_TICKS as f32);
+    index.clamp(INFLATION_MIN_INDEX, INFLATION_MAX_INDEX)
+}
+
+// - systems
+fn init_inflation_system(mut cmds: Commands) {
+    cmds.insert_resource(Inflation::default());
+}
+
+fn inflation_system(
+    mut inflation: ResMut<Inflation>,
+    mut market: ResMut<Market>,
+    ledger: Res<Ledger>,
+) {
+    let (printed, sunk) = money_flow(&ledger.tick);
+    inflation.tick(printed, sunk);
+    market.index = inflation.index;
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+pub struct InflationText;
+
+fn update_inflation_text_system(
+    mut texts: Query<&mut UIText, With<InflationText>>,
+    inflation: Res<Inflation>,
+) {
+    if !inflation.is_changed() {
+        return;
+    }
+
+    texts.iter_mut().for_each(|mut text| {
+        text.text = inflation.label();
+        text.color = if inflation.rate > 0.01 {
+            PICO8_ORANGE
+        } else {
+            PICO8_LIGHT_GRAY
+        };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::{ecs::bevy_ecs::schedule::Schedule, math::UVec2};
+
+    use super::*;
+    use crate::{
+        calendar::Calendar,
+        camera::CamShake,
+        economy::{
+            Money, Stock, TotalStock, market_recover_system, production_system, shop_system,
+            storage_system, total_stock_system,
+        },
+        game::{BuildKind, Building, Land},
+        ledger::roll_ledger_system,
+        notify::Notifications,
+        prestige::Prestige,
+        rules::Rules,
+        seed::SeedRng,
+        society::{Society, happiness_system, taxes_system},
+        terrain::Terrain,
+        upkeep::upkeep_system,
+    };
+
+    /// Mine, farm, house, forest and a shop selling what they make
+    const TOWN: [(BuildKind, UVec2); 5] = [
+        (BuildKind::Mine, UVec2::new(0, 0)),
+        (BuildKind::Farm, UVec2::new(1, 0)),
+        (BuildKind::House, UVec2::new(2, 0)),
+        (BuildKind::Forest, UVec2::new(0, 1)),
+        (BuildKind::Shop, UVec2::new(1, 1)),
+    ];
+
+    /// Runs the production, shops, taxes, upkeep and inflation of a land
+    /// with the buildings, without the engine, for the days given
+    fn run_economy(buildings: &[(BuildKind, UVec2)], days: u32) -> Inflation {
+        let mut world = World::new();
+        world.insert_resource(Money(STARTING_MONEY));
+        world.insert_resource(Market::default());
+        world.insert_resource(TotalStock::default());
+        world.insert_resource(Ledger::default());
+        world.insert_resource(Inflation::default());
+        world.insert_resource(Society::default());
+        world.insert_resource(Calendar::default());
+        world.insert_resource(Prestige::default());
+        world.insert_resource(Notifications::default());
+        world.insert_resource(CamShake::default());
+        world.insert_resource(Rules::default());
+
+        let mut land = Land::new(&mut SeedRng::new(0));
+        land.terrain.fill(Terrain::Plain);
+        land.owned = true;
+        let land_e = world.spawn((land, Stock::default())).id();
+        buildings.iter().for_each(|(kind, pos)| {
+            world.spawn((Building::new(land_e, *pos), *kind));
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                storage_system,
+                production_system,
+                shop_system,
+                market_recover_system,
+                total_stock_system,
+                happiness_system,
+                taxes_system,
+                upkeep_system,
+                inflation_system,
+                roll_ledger_system,
+            )
+                .chain(),
+        );
+        (0..days * DAY_TICKS).for_each(|_| schedule.run(&mut world));
+        world.remove_resource::<Inflation>().unwrap()
+    }
+
+    #[test]
+    fn profitable_town_inflates_gently() {
+        let inflation = run_economy(&TOWN, 30);
+        assert!(inflation.rate > 0.0, "rate {}", inflation.rate);
+        assert!(
+            (1.0..1.5).contains(&inflation.index),
+            "index {}",
+            inflation.index
+        );
+    }
+
+    #[test]
+    fn upkeep_of_idle_factories_deflates() {
+        // factories without gold only cost their upkeep
+        let factories = [UVec2::new(2, 1), UVec2::new(0, 2), UVec2::new(1, 2)]
+            .map(|pos| (BuildKind::Factory, pos));
+        let buildings = [TOWN.as_slice(), factories.as_slice()].concat();
+
+        let inflation = run_economy(&buildings, 30);
+        assert!(inflation.rate < 0.0, "rate {}", inflation.rate);
+        assert!(
+            (0.6..1.0).contains(&inflation.index),
+            "index {}",
+            inflation.index
+        );
+    }
+}
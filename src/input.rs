@@ -0,0 +1,358 @@
+use rkit::{math::Vec2, prelude::*};
+
+use crate::{consts::*, platform};
+
+pub fn input_plugin(app: &mut App) {
+    app.add_resource(Pointer::default())
+        .add_systems(OnSetup, init_touch_system)
+        .add_systems(OnPreUpdate, update_pointer_system);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
+/// Raw touch in screen coordinates as it comes from the platform
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchEvent {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub pos: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    Tap(Vec2),
+    LongPress(Vec2),
+    /// One finger drag, screen delta since the last move
+    Pan(Vec2),
+    /// Two fingers, ratio between the distance now and on the last move
+    Pinch {
+        center: Vec2,
+        scale: f32,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ActiveTouch {
+    id: u64,
+    start: Vec2,
+    pos: Vec2,
+    time: f32,
+}
+
+/// Turns touch sequences into gestures, it does not know about the
+/// engine so it can be driven by hand with synthetic events
+#[derive(Clone, Debug, Default)]
+pub struct TouchGestures {
+    touches: Vec<ActiveTouch>,
+    /// The current touch cannot become a tap anymore
+    consumed: bool,
+}
+
+impl TouchGestures {
+    pub fn feed(&mut self, event: TouchEvent, time: f32) -> Option<Gesture> {
+        match event.phase {
+            TouchPhase::Start => {
+                if self.touches.is_empty() {
+                    self.consumed = false;
+                }
+
+                // a second finger turns it into a pinch
+                self.consumed |= !self.touches.is_empty();
+                self.touches.push(ActiveTouch {
+                    id: event.id,
+                    start: event.pos,
+                    pos: event.pos,
+                    time,
+                });
+                None
+            }
+            TouchPhase::Move => self.on_move(event),
+            TouchPhase::End | TouchPhase::Cancel => {
+                let idx = self.touches.iter().position(|t| t.id == event.id)?;
+                let touch = self.touches.remove(idx);
+                let is_tap = event.phase == TouchPhase::End
+                    && !self.consumed
+                    && self.touches.is_empty()
+                    && time - touch.time < TOUCH_LONG_PRESS_TIME;
+                is_tap.then_some(Gesture::Tap(touch.start))
+            }
+        }
+    }
+
+    /// Long press is detected while the finger is still down
+    pub fn update(&mut self, time: f32) -> Option<Gesture> {
+        let [touch] = self.touches.as_slice() else {
+            return None;
+        };
+
+        let is_long = !self.consumed && time - touch.time >= TOUCH_LONG_PRESS_TIME;
+        if !is_long {
+            return None;
+        }
+
+        self.consumed = true;
+        Some(Gesture::LongPress(touch.pos))
+    }
+
+    fn on_move(&mut self, event: TouchEvent) -> Option<Gesture> {
+        let idx = self.touches.iter().position(|t| t.id == event.id)?;
+        let last = self.touches[idx].pos;
+        self.touches[idx].pos = event.pos;
+
+        match self.touches.as_slice() {
+            [touch] => {
+                // small moves are still part of a tap
+                if !self.consumed && touch.start.distance(event.pos) < TOUCH_TAP_DISTANCE {
+                    return None;
+                }
+
+                self.consumed = true;
+                Some(Gesture::Pan(event.pos - last))
+            }
+            [a, b, ..] => {
+                let other = if idx == 0 { b.pos } else { a.pos };
+                let before = other.distance(last);
+                let now = other.distance(event.pos);
+                (before > 0.0).then(|| Gesture::Pinch {
+                    center: (other + event.pos) * 0.5,
+                    scale: now / before,
+                })
+            }
+            [] => None,
+        }
+    }
+}
+
+/// Mouse and touch merged, what the game reads instead of the `Mouse`
+#[derive(Resource, Default)]
+pub struct Pointer {
+    /// Screen position of the mouse or the last touch
+    pub position: Vec2,
+    /// Left click or tap this frame
+    pub click: bool,
+    /// Left button or a finger went down this frame
+    pub pressed: bool,
+    /// Left button or a finger is down
+    pub down: bool,
+    /// Right click without dragging or long press this frame
+    pub secondary: bool,
+    /// Screen delta of a one finger drag this frame
+    pub pan: Vec2,
+    /// Zoom steps from the wheel or the pinch this frame
+    pub zoom: i32,
+    /// Topmost ui node under the pointer
+    pub ui_node: Option<Entity>,
+    /// Ui node under the press, the drag belongs to it until released
+    pub ui_grab: Option<Entity>,
+    gestures: TouchGestures,
+    /// Pinch ratio accumulated until it is big enough for a step
+    pinch: f32,
+    right_press: Option<Vec2>,
+}
+
+impl Pointer {
+    fn apply(&mut self, gesture: Gesture) {
+        match gesture {
+            Gesture::Tap(pos) => {
+                self.position = pos;
+                self.click = true;
+            }
+            Gesture::LongPress(pos) => {
+                self.position = pos;
+                self.secondary = true;
+            }
+            Gesture::Pan(delta) => self.pan += delta,
+            Gesture::Pinch { center, scale } => {
+                self.position = center;
+                self.pinch *= scale;
+                if self.pinch >= TOUCH_PINCH_STEP {
+                    self.zoom += 1;
+                    self.pinch = 1.0;
+                } else if self.pinch <= 1.0 / TOUCH_PINCH_STEP {
+                    self.zoom -= 1;
+                    self.pinch = 1.0;
+                }
+            }
+        }
+    }
+}
+
+// - systems
+fn init_touch_system() {
+    platform::init_touch();
+}
+
+pub fn update_pointer_system(mut pointer: ResMut<Pointer>, mouse: Res<Mouse>, time: Res<Time>) {
+    let now = time.elapsed_f32();
+    pointer.click = mouse.just_pressed(MouseButton::Left);
+    pointer.pressed = mouse.just_pressed(MouseButton::Left);
+    pointer.down = mouse.is_down(MouseButton::Left);
+    pointer.secondary = false;
+    pointer.pan = Vec2::ZERO;
+    pointer.zoom = match mouse.wheel_delta().y {
+        y if y > 0.0 => 1,
+        y if y < 0.0 => -1,
+        _ => 0,
+    };
+
+    // right click only if the mouse did not drag the camera
+    let mouse_pos = mouse.position();
+    if mouse.just_pressed(MouseButton::Right) {
+        pointer.right_press = Some(mouse_pos);
+    }
+    if mouse.just_released(MouseButton::Right) {
+        let press = pointer.right_press.take();
+        pointer.secondary = press.is_some_and(|p| p.distance(mouse_pos) < TOUCH_TAP_DISTANCE);
+    }
+
+    let events = platform::take_touches();
+    if events.is_empty() && pointer.gestures.touches.is_empty() {
+        pointer.position = mouse_pos;
+        return;
+    }
+
+    if pointer.gestures.touches.is_empty() {
+        pointer.pinch = 1.0;
+    }
+
+    events.into_iter().for_each(|event| {
+        pointer.position = event.pos;
+        pointer.pressed |= event.phase == TouchPhase::Start && pointer.gestures.touches.is_empty();
+        if let Some(gesture) = pointer.gestures.feed(event, now) {
+            pointer.apply(gesture);
+        }
+    });
+
+    if let Some(gesture) = pointer.gestures.update(now) {
+        pointer.apply(gesture);
+    }
+    pointer.down |= !pointer.gestures.touches.is_empty();
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::math::vec2;
+
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchEvent {
+        TouchEvent {
+            id,
+            phase,
+            pos: vec2(x, y),
+        }
+    }
+
+    #[test]
+    fn quick_touch_is_a_tap() {
+        let mut gestures = TouchGestures::default();
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Start, 10.0, 10.0), 0.0),
+            None
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Move, 12.0, 11.0), 0.1),
+            None
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::End, 12.0, 11.0), 0.2),
+            Some(Gesture::Tap(vec2(10.0, 10.0)))
+        );
+    }
+
+    #[test]
+    fn long_press_is_not_a_tap() {
+        let mut gestures = TouchGestures::default();
+        gestures.feed(touch(0, TouchPhase::Start, 10.0, 10.0), 0.0);
+        assert_eq!(gestures.update(0.3), None);
+        assert_eq!(
+            gestures.update(TOUCH_LONG_PRESS_TIME),
+            Some(Gesture::LongPress(vec2(10.0, 10.0)))
+        );
+        assert_eq!(gestures.update(1.0), None);
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::End, 10.0, 10.0), 1.1),
+            None
+        );
+    }
+
+    #[test]
+    fn drag_past_the_tap_distance_pans() {
+        let mut gestures = TouchGestures::default();
+        gestures.feed(touch(0, TouchPhase::Start, 0.0, 0.0), 0.0);
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Move, 5.0, 0.0), 0.1),
+            None
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Move, TOUCH_TAP_DISTANCE, 0.0), 0.2),
+            Some(Gesture::Pan(vec2(TOUCH_TAP_DISTANCE - 5.0, 0.0)))
+        );
+
+        // once panning even small moves pan
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Move, 5.0, 0.0), 0.3),
+            Some(Gesture::Pan(vec2(5.0 - TOUCH_TAP_DISTANCE, 0.0)))
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::End, 5.0, 0.0), 0.4),
+            None
+        );
+    }
+
+    #[test]
+    fn two_fingers_pinch() {
+        let mut gestures = TouchGestures::default();
+        gestures.feed(touch(0, TouchPhase::Start, 0.0, 0.0), 0.0);
+        gestures.feed(touch(1, TouchPhase::Start, 10.0, 0.0), 0.0);
+        assert_eq!(
+            gestures.feed(touch(1, TouchPhase::Move, 20.0, 0.0), 0.1),
+            Some(Gesture::Pinch {
+                center: vec2(10.0, 0.0),
+                scale: 2.0,
+            })
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Move, 10.0, 0.0), 0.2),
+            Some(Gesture::Pinch {
+                center: vec2(15.0, 0.0),
+                scale: 0.5,
+            })
+        );
+
+        // lifting the fingers is not a tap
+        assert_eq!(
+            gestures.feed(touch(1, TouchPhase::End, 20.0, 0.0), 0.3),
+            None
+        );
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::End, 10.0, 0.0), 0.3),
+            None
+        );
+        assert_eq!(gestures.update(1.0), None);
+    }
+
+    #[test]
+    fn cancel_is_not_a_tap() {
+        let mut gestures = TouchGestures::default();
+        gestures.feed(touch(0, TouchPhase::Start, 10.0, 10.0), 0.0);
+        assert_eq!(
+            gestures.feed(touch(0, TouchPhase::Cancel, 10.0, 10.0), 0.1),
+            None
+        );
+        assert_eq!(gestures.update(1.0), None);
+
+        // the next touch starts clean
+        gestures.feed(touch(1, TouchPhase::Start, 20.0, 20.0), 2.0);
+        assert_eq!(
+            gestures.feed(touch(1, TouchPhase::End, 20.0, 20.0), 2.1),
+            Some(Gesture::Tap(vec2(20.0, 20.0)))
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::VecDeque;
+
+use rkit::{gfx::Color, math::Vec2, prelude::*};
+use rustc_hash::FxHashMap;
+use strum::IntoEnumIterator;
+
+use crate::{
+    consts::*,
+    economy::SimSet,
+    game::{BuildKind, ResourceKind},
+    screens::AppScreen,
+    stats::{StatKey, add_tooltip_sparkline},
+    ui::{
+        UIGameLayout,
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+        tooltip::{TooltipContainer, TooltipNode, clear_tooltips, create_text_tooltip},
+    },
+};
+
+pub fn ledger_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_ledger_system)
+        .add_screen_systems(screen, OnUpdate, SimSet::Record.on_tick(roll_ledger_system))
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            (update_ledger_panel_system, ledger_tooltip_system),
+        );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LedgerCategory {
+    Building(BuildKind),
+    Sales,
+    Purchases,
+    Contracts,
+    Taxes,
+    Upkeep,
+    Construction,
+    Loans,
+    Interest,
+    /// Contract penalties
+    Events,
+}
+
+impl LedgerCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LedgerCategory::Building(kind) => kind.name(),
+            LedgerCategory::Sales => "Sales",
+            LedgerCategory::Purchases => "Purchases",
+            LedgerCategory::Contracts => "Contracts",
+            LedgerCategory::Taxes => "Taxes",
+            LedgerCategory::Upkeep => "Upkeep",
+            LedgerCategory::Construction => "Construction",
+            LedgerCategory::Loans => "Loans",
+            LedgerCategory::Interest => "Interest",
+            LedgerCategory::Events => "Events",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LedgerDelta {
+    pub money: f32,
+    pub resources: FxHashMap<ResourceKind, f32>,
+}
+
+/// Deltas of each category during a minute of game time
+#[derive(Clone, Debug, Default)]
+pub struct LedgerMinute {
+    pub deltas: FxHashMap<LedgerCategory, LedgerDelta>,
+}
+
+impl LedgerMinute {
+    pub fn net_money(&self) -> f32 {
+        self.deltas.values().map(|d| d.money).sum()
+    }
+
+    pub fn money(&self, category: LedgerCategory) -> f32 {
+        self.deltas.get(&category).map_or(0.0, |d| d.money)
+    }
+
+    /// Resource deltas of the category, in the order of the resources
+    pub fn resources(
+        &self,
+        category: LedgerCategory,
+    ) -> impl Iterator<Item = (ResourceKind, f32)> + '_ {
+        let delta = self.deltas.get(&category);
+        ResourceKind::iter()
+            .filter_map(move |kind| delta?.resources.get(&kind).map(|amount| (kind, *amount)))
+    }
+
+    pub fn income(&self) -> f32 {
+        self.deltas.values().map(|d| d.money.max(0.0)).sum()
+    }
+
+    pub fn expenses(&self) -> f32 {
+        self.deltas.values().map(|d| d.money.min(0.0)).sum()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Ledger {
+    pub current: LedgerMinute,
+    /// Deltas booked since the last simulation tick was recorded
+    pub tick: LedgerMinute,
+    /// Last minutes, the most recent first
+    pub history: VecDeque<LedgerMinute>,
+    ticks: u32,
+}
+
+impl Ledger {
+    pub fn money(&mut self, category: LedgerCategory, amount: f32) {
+        [&mut self.current, &mut self.tick]
+            .into_iter()
+            .for_each(|minute| minute.deltas.entry(category).or_default().money += amount);
+    }
+
+    pub fn resource(&mut self, category: LedgerCategory, kind: ResourceKind, amount: f32) {
+        [&mut self.current, &mut self.tick]
+            .into_iter()
+            .for_each(|minute| {
+                *minute
+                    .deltas
+                    .entry(category)
+                    .or_default()
+                    .resources
+                    .entry(kind)
+                    .or_default() += amount;
+            });
+    }
+
+    pub fn last_minute(&self) -> Option<&LedgerMinute> {
+        self.history.front()
+    }
+
+    fn categories(&self) -> Vec<LedgerCategory> {
+        let mut categories = self
+            .current
+            .deltas
+            .keys()
+            .chain(self.last_minute().iter().flat_map(|m| m.deltas.keys()))
+            .copied()
+            .collect::<Vec<_>>();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+}
+
+// - systems
+fn init_ledger_system(mut cmds: Commands) {
+    cmds.insert_resource(Ledger::default());
+}
+
+pub fn roll_ledger_system(mut ledger: ResMut<Ledger>) {
+    // the tick is recorded last, anything booked after belongs to the next one
+    ledger.tick = LedgerMinute::default();
+    ledger.ticks += 1;
+    let minute_ticks = (60.0 / SIM_TICK_TIME) as u32;
+    if ledger.ticks < minute_ticks {
+        return;
+    }
+
+    ledger.ticks = 0;
+    let minute = std::mem::take(&mut ledger.current);
+    ledger.history.push_front(minute);
+    ledger.history.truncate(LEDGER_HISTORY_MINUTES);
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct LedgerPanel;
+
+#[derive(Component, Clone, Copy)]
+struct LedgerTable;
+
+#[derive(Component, Clone, Copy)]
+struct LedgerRow;
+
+/// Opens the ledger on click and shows a summary on hover
+#[derive(Component, Clone, Copy)]
+pub struct LedgerInfo;
+
+pub fn ledger_info() -> (LedgerInfo, UIPointer, UIOnClick) {
+    (
+        LedgerInfo,
+        UIPointer::default(),
+        UIOnClick::run(toggle_ledger_panel_system),
+    )
+}
+
+fn toggle_ledger_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<LedgerPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Ledger", LedgerPanel);
+        let header = spawn_panel_node(cmds, panel, content, panel_row());
+        spawn_panel_node(cmds, panel, header, panel_text("Category", PICO8_INDIGO));
+        spawn_panel_node(
+            cmds,
+            panel,
+            header,
+            panel_text("This min / Last min", PICO8_INDIGO),
+        );
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (
+                LedgerTable,
+                UIContainer::default(),
+                UIStyle::default().flex_col().gap_y(1.0),
+            ),
+        );
+    });
+}
+
+fn money_color(amount: f32) -> Color {
+    if amount < 0.0 {
+        PICO8_RED
+    } else if amount > 0.0 {
+        PICO8_GREEN
+    } else {
+        PICO8_LIGHT_GRAY
+    }
+}
+
+fn update_ledger_panel_system(
+    mut cmds: Commands,
+    table: Query<(Entity, &PanelNode), With<LedgerTable>>,
+    new_table: Query<(), Added<LedgerTable>>,
+    rows: Query<Entity, With<LedgerRow>>,
+    ledger: Res<Ledger>,
+) {
+    let Ok((table, panel)) = table.get_single() else {
+        return;
+    };
+
+    if !ledger.is_changed() && new_table.is_empty() {
+        return;
+    }
+
+    let layout = UIGameLayout;
+    rows.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+
+    let last = ledger.last_minute();
+    ledger.categories().iter().for_each(|category| {
+        let now = ledger.current.money(*category);
+        let before = last.map(|m| m.money(*category));
+        add_ledger_row(&mut cmds, panel.0, table, category.name(), now, before);
+        add_resources_row(&mut cmds, panel.0, table, &ledger.current, *category);
+    });
+
+    add_ledger_row(
+        &mut cmds,
+        panel.0,
+        table,
+        "Net total",
+        ledger.current.net_money(),
+        last.map(|m| m.net_money()),
+    );
+}
+
+fn add_ledger_row(
+    cmds: &mut Commands,
+    panel: Entity,
+    table: Entity,
+    name: &str,
+    now: f32,
+    before: Option<f32>,
+) {
+    let row = spawn_panel_node(cmds, panel, table, (LedgerRow, panel_row()));
+    spawn_panel_node(cmds, panel, row, (LedgerRow, panel_text(name, PICO8_WHITE)));
+
+    let before = before.map_or("-".to_string(), |b| format!("{b:+.0}"));
+    spawn_panel_node(
+        cmds,
+        panel,
+        row,
+        (
+            LedgerRow,
+            panel_text(format!("{now:+.0} / {before}"), money_color(now)),
+        ),
+    );
+}
+
+/// Resources gained and spent by the category during this minute
+fn add_resources_row(
+    cmds: &mut Commands,
+    panel: Entity,
+    table: Entity,
+    minute: &LedgerMinute,
+    category: LedgerCategory,
+) {
+    let deltas = minute
+        .resources(category)
+        .filter(|(_, amount)| amount.abs() >= 0.5)
+        .map(|(kind, amount)| format!("{amount:+.0} {}", kind.name()))
+        .collect::<Vec<_>>();
+
+    if deltas.is_empty() {
+        return;
+    }
+
+    spawn_panel_node(
+        cmds,
+        panel,
+        table,
+        (
+            LedgerRow,
+            panel_text(format!("  {}", deltas.join(" ")), PICO8_LIGHT_GRAY),
+        ),
+    );
+}
+
+fn ledger_tooltip_system(
+    mut cmds: Commands,
+    query: Query<(&UIPointer, &UINode), With<LedgerInfo>>,
+    tooltip_container: Single<Entity, With<TooltipContainer>>,
+    tooltip_nodes: Query<Entity, With<TooltipNode>>,
+    ledger: Res<Ledger>,
+) {
+    let layout = UIGameLayout;
+    let tooltip_container = tooltip_container.into_inner();
+    query.iter().for_each(|(pointer, node)| {
+        if pointer.just_enter() {
+            let minute = ledger.last_minute().unwrap_or(&ledger.current);
+            let lines = [
+                (format!("Income: {:+.0}", minute.income()), PICO8_GREEN),
+                (format!("Expenses: {:+.0}", minute.expenses()), PICO8_RED),
+                (
+                    format!("Net: {:+.0}", minute.net_money()),
+                    money_color(minute.net_money()),
+                ),
+                ("Click to open the ledger".to_string(), PICO8_LIGHT_GRAY),
+            ];
+            let pos = node.position() + node.size() * Vec2::Y;
+            let tooltip = create_text_tooltip(&mut cmds, layout, "Money", &lines, pos);
+            cmds.add_ui_child(layout, tooltip_container, tooltip);
+            add_tooltip_sparkline(&mut cmds, tooltip, StatKey::Money, PICO8_YELLOW);
+        } else if pointer.just_exit() {
+            clear_tooltips(&mut cmds, layout, &tooltip_nodes);
+        }
+    });
+}
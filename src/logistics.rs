@@ -0,0 +1,404 @@
+use rkit::{math::Vec2, prelude::*};
+use rustc_hash::FxHashMap;
+use strum::IntoEnumIterator;
+
+use crate::{
+    components::Pos,
+    consts::*,
+    economy::{SimSet, Stock},
+    game::{BuildKind, Building, Land, ResourceKind, Shutdown},
+    notify::Notifications,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::create_text_btn,
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+    worldgen::land_label,
+};
+
+pub fn logistics_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_logistics_system)
+        .add_systems(OnExit(screen), cleanup_logistics_system)
+        .add_screen_systems(screen, OnUpdate, SimSet::Trade.on_tick(dispatch_system))
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            (move_shipments_system, update_routes_panel_system),
+        );
+}
+
+/// Moves a resource from one land to another using the depots of the origin
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub from: Entity,
+    pub to: Entity,
+    pub kind: ResourceKind,
+}
+
+/// Goods in transit between two lands
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Shipment {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub dest: Entity,
+    pub kind: ResourceKind,
+    pub amount: f32,
+    pub elapsed: f32,
+    pub duration: f32,
+}
+
+impl Shipment {
+    pub fn position(&self) -> Vec2 {
+        let t = (self.elapsed / self.duration).min(1.0);
+        self.from.lerp(self.to, t)
+    }
+}
+
+// - systems
+fn init_logistics_system(mut cmds: Commands) {
+    cmds.insert_resource(RouteDraft {
+        from: None,
+        to: None,
+        kind: ResourceKind::Copper,
+    });
+}
+
+fn cleanup_logistics_system(
+    mut cmds: Commands,
+    query: Query<Entity, Or<(With<Route>, With<Shipment>)>>,
+) {
+    query.iter().for_each(|e| cmds.entity(e).despawn());
+}
+
+/// Each tick the depots of a land send goods through its routes, the
+/// throughput is shared by all the routes leaving the land
+fn dispatch_system(
+    mut cmds: Commands,
+    routes: Query<&Route>,
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+    mut lands: Query<(&Land, &Pos, &mut Stock)>,
+) {
+    let mut throughput = FxHashMap::<Entity, f32>::default();
+    buildings
+        .iter()
+        .filter(|(kind, _)| matches!(kind, BuildKind::Depot))
+        .for_each(|(_, building)| {
+            *throughput.entry(building.land).or_default() += building.level as f32 * TRANSPORT_RATE;
+        });
+
+    routes.iter().for_each(|route| {
+        let Some(capacity) = throughput.get_mut(&route.from) else {
+            return;
+        };
+
+        let Ok((to_land, to_pos, _)) = lands.get(route.to) else {
+            return;
+        };
+
+        if !to_land.owned {
+            return;
+        }
+
+        let to = to_pos.0;
+        let Ok((_, from_pos, mut stock)) = lands.get_mut(route.from) else {
+            return;
+        };
+
+        let amount = stock.get(route.kind).min(*capacity).floor();
+        if amount <= 0.0 || !stock.take(route.kind, amount) {
+            return;
+        }
+
+        *capacity -= amount;
+        let from = from_pos.0;
+        cmds.spawn(Shipment {
+            from,
+            to,
+            dest: route.to,
+            kind: route.kind,
+            amount,
+            elapsed: 0.0,
+            duration: from.distance(to) / TRANSPORT_SPEED,
+        });
+    });
+}
+
+fn move_shipments_system(
+    mut cmds: Commands,
+    mut shipments: Query<(Entity, &mut Shipment)>,
+    mut stocks: Query<&mut Stock>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_f32();
+    shipments.iter_mut().for_each(|(entity, mut shipment)| {
+        shipment.elapsed += dt;
+        if shipment.elapsed < shipment.duration {
+            return;
+        }
+
+        if let Ok(mut stock) = stocks.get_mut(shipment.dest) {
+            let wasted = stock.add(shipment.kind, shipment.amount);
+            if wasted > 0.0 {
+                log::debug!("{wasted:.2} of {:?} lost on arrival", shipment.kind);
+            }
+        }
+        cmds.entity(entity).despawn();
+    });
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct RoutesPanel;
+
+#[derive(Component, Clone, Copy)]
+struct RoutesList;
+
+#[derive(Component, Clone, Copy)]
+struct RouteRow;
+
+#[derive(Component, Clone, Copy)]
+struct RemoveRouteBtn(Entity);
+
+#[derive(Component, Clone, Copy)]
+struct DraftText;
+
+/// Route being edited before adding it
+#[derive(Resource, Clone, Copy)]
+struct RouteDraft {
+    from: Option<Entity>,
+    to: Option<Entity>,
+    kind: ResourceKind,
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+enum DraftBtn {
+    From,
+    To,
+    Kind,
+    Add,
+}
+
+impl DraftBtn {
+    fn text(&self) -> &'static str {
+        match self {
+            DraftBtn::From => "From",
+            DraftBtn::To => "To",
+            DraftBtn::Kind => "Res",
+            DraftBtn::Add => "Add",
+        }
+    }
+}
+
+pub fn create_routes_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Routes",
+        48.0,
+        UIOnClick::run(toggle_routes_panel_system),
+    )
+}
+
+fn toggle_routes_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<RoutesPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Routes", RoutesPanel);
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            panel_text("Depots send goods to other lands", PICO8_LIGHT_GRAY),
+        );
+
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (
+                RoutesList,
+                UIContainer::default(),
+                UIStyle::default().flex_col().gap_y(1.0),
+            ),
+        );
+
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (DraftText, panel_text("", PICO8_WHITE)),
+        );
+
+        let row = spawn_panel_node(cmds, panel, content, panel_row());
+        [DraftBtn::From, DraftBtn::To, DraftBtn::Kind, DraftBtn::Add]
+            .into_iter()
+            .for_each(|field| {
+                let btn = create_text_btn(
+                    cmds,
+                    UIGameLayout,
+                    field.text(),
+                    32.0,
+                    (PanelNode(panel), field, UIOnClick::run(edit_draft_system)),
+                );
+                cmds.add_ui_child(UIGameLayout, row, btn);
+            });
+    });
+}
+
+/// Owned lands sorted by their position in the world
+fn owned_lands(lands: &Query<(Entity, &Land, &Pos)>) -> Vec<Entity> {
+    let mut owned = lands
+        .iter()
+        .filter(|(_, land, _)| land.owned)
+        .map(|(entity, _, pos)| (entity, pos.0))
+        .collect::<Vec<_>>();
+    owned.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    owned.into_iter().map(|(entity, _)| entity).collect()
+}
+
+fn next_land(lands: &[Entity], current: Option<Entity>) -> Option<Entity> {
+    let idx = current
+        .and_then(|c| lands.iter().position(|e| *e == c))
+        .map_or(0, |i| i + 1);
+    lands.get(idx % lands.len().max(1)).copied()
+}
+
+fn edit_draft_system(
+    In(entity): In<Entity>,
+    mut cmds: Commands,
+    btns: Query<&DraftBtn>,
+    lands: Query<(Entity, &Land, &Pos)>,
+    routes: Query<&Route>,
+    mut draft: ResMut<RouteDraft>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    match btn {
+        DraftBtn::From => draft.from = next_land(&owned_lands(&lands), draft.from),
+        DraftBtn::To => draft.to = next_land(&owned_lands(&lands), draft.to),
+        DraftBtn::Kind => {
+            let kinds = ResourceKind::iter()
+                .filter(|k| !matches!(k, ResourceKind::People))
+                .collect::<Vec<_>>();
+            let idx = kinds.iter().position(|k| *k == draft.kind).unwrap_or(0);
+            draft.kind = kinds[(idx + 1) % kinds.len()];
+        }
+        DraftBtn::Add => {
+            let (Some(from), Some(to)) = (draft.from, draft.to) else {
+                notifications.push("Choose the lands of the route", PICO8_ORANGE, None);
+                return;
+            };
+
+            if from == to {
+                notifications.push("A route needs two lands", PICO8_ORANGE, None);
+                return;
+            }
+
+            let route = Route {
+                from,
+                to,
+                kind: draft.kind,
+            };
+            if routes.iter().any(|r| *r == route) {
+                return;
+            }
+
+            if routes.iter().count() >= MAX_ROUTES {
+                notifications.push("Too many routes", PICO8_ORANGE, None);
+                return;
+            }
+
+            cmds.spawn(route);
+        }
+    }
+}
+
+fn remove_route_system(In(entity): In<Entity>, mut cmds: Commands, btns: Query<&RemoveRouteBtn>) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    cmds.entity(btn.0).despawn();
+}
+
+fn update_routes_panel_system(
+    mut cmds: Commands,
+    mut draft_text: Query<&mut UIText, With<DraftText>>,
+    list: Query<(Entity, &PanelNode), With<RoutesList>>,
+    new_list: Query<(), Added<RoutesList>>,
+    new_routes: Query<(), Added<Route>>,
+    mut removed_routes: RemovedComponents<Route>,
+    rows: Query<Entity, With<RouteRow>>,
+    routes: Query<(Entity, &Route)>,
+    positions: Query<&Pos, With<Land>>,
+    draft: Res<RouteDraft>,
+) {
+    let removed = removed_routes.read().count() > 0;
+    let Ok((list, panel)) = list.get_single() else {
+        return;
+    };
+
+    let label = |land: Option<Entity>| {
+        land.and_then(|e| positions.get(e).ok())
+            .map_or("?".to_string(), |pos| land_label(pos.0))
+    };
+
+    draft_text.iter_mut().for_each(|mut text| {
+        text.text = format!(
+            "New: {} > {} {}",
+            label(draft.from),
+            label(draft.to),
+            draft.kind.name()
+        );
+    });
+
+    // rows are rebuilt only when a route is added or removed
+    if new_list.is_empty() && new_routes.is_empty() && !removed {
+        return;
+    }
+
+    let layout = UIGameLayout;
+    rows.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+    routes.iter().for_each(|(entity, route)| {
+        let row = spawn_panel_node(&mut cmds, panel.0, list, (RouteRow, panel_row()));
+        let txt = format!(
+            "{} > {} {}",
+            label(Some(route.from)),
+            label(Some(route.to)),
+            route.kind.name()
+        );
+        spawn_panel_node(
+            &mut cmds,
+            panel.0,
+            row,
+            (RouteRow, panel_text(txt, PICO8_PEACH)),
+        );
+        let btn = create_text_btn(
+            &mut cmds,
+            layout,
+            "X",
+            12.0,
+            (
+                PanelNode(panel.0),
+                RouteRow,
+                RemoveRouteBtn(entity),
+                UIOnClick::run(remove_route_system),
+            ),
+        );
+        cmds.add_ui_child(layout, row, btn);
+    });
+}
@@ -1,19 +1,50 @@
 mod assets;
+mod bank;
+mod build;
+mod calendar;
 mod camera;
 mod components;
 mod consts;
+mod contracts;
+mod economy;
+mod excavation;
 mod game;
+mod hazards;
+mod inflation;
+mod input;
+mod ledger;
+mod logistics;
+mod minimap;
+mod notify;
+mod platform;
+mod pollution;
 mod postfx;
+mod prestige;
 mod render;
+mod rivals;
+mod rules;
+mod save;
 mod screens;
+mod seed;
+mod settings;
+mod society;
+mod stats;
+mod terrain;
+mod tween;
 mod ui;
+mod undo;
+mod upkeep;
+mod worldgen;
 
 use camera::camera_plugin;
 use consts::*;
+use input::input_plugin;
 use postfx::post_fx_plugin;
 use render::render_plugin;
 use rkit::prelude::*;
 use screens::screens_plugin;
+use settings::settings_plugin;
+use tween::tween_plugin;
 use ui::ui_plugin;
 
 pub fn main() -> Result<(), String> {
@@ -25,7 +56,10 @@ pub fn main() -> Result<(), String> {
         .add_plugin(window_plugin())
         .add_plugin(logging_plugin())
         // game plugins
+        .add_plugin(input_plugin)
+        .add_plugin(settings_plugin)
         .add_plugin(camera_plugin)
+        .add_plugin(tween_plugin)
         .add_plugin(ui_plugin)
         .add_plugin(screens_plugin)
         .add_plugin(render_plugin)
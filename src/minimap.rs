@@ -0,0 +1,104 @@
+use rkit::{gfx::Color, math::Rect, prelude::*};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    camera::{Cam, GameCam, UICam, focus_game_cam},
+    components::Pos,
+    consts::*,
+    game::{BuildKind, Building, Land},
+    rivals::Rival,
+    screens::AppScreen,
+    ui::{UIGameLayout, click::UIOnClick, minimap::UIMinimap},
+};
+
+pub fn minimap_plugin(app: &mut App) {
+    app.add_screen_systems(AppScreen::Game, OnUpdate, update_minimap_system);
+}
+
+/// Colour of the lands where the kind is the most common building
+fn building_color(kind: BuildKind) -> Color {
+    match kind {
+        BuildKind::Farm => PICO8_GREEN,
+        BuildKind::House => PICO8_PEACH,
+        BuildKind::Forest => PICO8_DARK_GREEN,
+        BuildKind::Factory => PICO8_RED,
+        BuildKind::Shop => PICO8_YELLOW,
+        BuildKind::Mine => PICO8_BROWN,
+        BuildKind::Warehouse => PICO8_INDIGO,
+        BuildKind::Depot => PICO8_LIGHT_GRAY,
+    }
+}
+
+pub fn create_minimap(cmds: &mut Commands) -> Entity {
+    cmds.spawn_ui_node(
+        UIGameLayout,
+        (
+            UIMinimap::default(),
+            UIStyle::default().size(MINIMAP_SIZE.x, MINIMAP_SIZE.y),
+            UIOnClick::run(on_minimap_click_system),
+        ),
+    )
+    .entity_id()
+}
+
+// - systems
+fn update_minimap_system(
+    mut minimap: Single<&mut UIMinimap>,
+    lands: Query<(&Land, &Pos)>,
+    buildings: Query<&BuildKind, With<Building>>,
+    rivals: Query<&Rival>,
+    cam: Single<&Cam, With<GameCam>>,
+) {
+    let mut bounds = None;
+    let rects = lands
+        .iter()
+        .map(|(land, pos)| {
+            let rect = land.bounds(pos.0);
+            bounds = Some(match bounds {
+                None => (rect.min(), rect.min() + rect.size),
+                Some((min, max)) => (min.min(rect.min()), max.max(rect.min() + rect.size)),
+            });
+
+            let mut counts = FxHashMap::default();
+            land.buildings
+                .iter()
+                .filter_map(|e| buildings.get(*e).ok())
+                .for_each(|kind| *counts.entry(*kind).or_insert(0) += 1);
+            let dominant = counts
+                .into_iter()
+                .max_by_key(|(kind, count)| (*count, *kind))
+                .map(|(kind, _)| kind);
+
+            let color = match (land.rival.and_then(|e| rivals.get(e).ok()), dominant) {
+                (Some(rival), _) => rival.color,
+                (None, Some(kind)) => building_color(kind),
+                (None, None) if land.owned => PICO8_DARK_GRAY,
+                (None, None) => PICO8_DARK_BLUE,
+            };
+            (rect, color)
+        })
+        .collect();
+
+    let Some((min, max)) = bounds else {
+        return;
+    };
+
+    minimap.bounds = Rect::new(min, max - min);
+    minimap.rects = rects;
+    minimap.view = cam.bounds();
+}
+
+fn on_minimap_click_system(
+    In(entity): In<Entity>,
+    mut cmds: Commands,
+    minimaps: Query<(&UIMinimap, &UINode)>,
+    cam: Single<&Cam, With<UICam>>,
+) {
+    let Ok((minimap, node)) = minimaps.get(entity) else {
+        return;
+    };
+
+    let local = cam.mouse_pos - node.position();
+    let target = minimap.to_world(local, node.size());
+    cmds.queue(focus_game_cam(target, None));
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use rkit::{draw::HAlign, gfx::Color, prelude::*};
+
+use crate::{
+    camera::focus_game_cam, components::Pos, consts::*, game::Building, screens::AppScreen,
+    ui::UIGameLayout,
+};
+
+pub fn notify_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_notifications_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            (
+                expire_notifications_system,
+                update_notifications_ui_system,
+                focus_notification_system,
+            )
+                .chain(),
+        );
+}
+
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub text: String,
+    pub color: Color,
+    /// Entity the notification refers to, if any
+    pub target: Option<Entity>,
+    time: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct Notifications {
+    items: VecDeque<Notification>,
+    /// Target of the last notification, the camera glides to it
+    focus: Option<Entity>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, text: impl Into<String>, color: Color, target: Option<Entity>) {
+        let text = text.into();
+        log::info!("Notification: {text}");
+        if target.is_some() {
+            self.focus = target;
+        }
+        self.items.push_front(Notification {
+            text,
+            color,
+            target,
+            time: 0.0,
+        });
+        self.items.truncate(NOTIFICATION_MAX);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+        self.items.iter()
+    }
+}
+
+/// Container where the notifications are listed
+#[derive(Component, Clone, Copy)]
+pub struct NotificationList;
+
+#[derive(Component, Clone, Copy)]
+struct NotificationItem;
+
+fn init_notifications_system(mut cmds: Commands) {
+    cmds.insert_resource(Notifications::default());
+}
+
+fn expire_notifications_system(mut notifications: ResMut<Notifications>, time: Res<Time>) {
+    let dt = time.delta_f32();
+    notifications
+        .bypass_change_detection()
+        .items
+        .iter_mut()
+        .for_each(|n| n.time += dt);
+
+    let expired = notifications
+        .items
+        .iter()
+        .any(|n| n.time >= NOTIFICATION_TIME);
+    if expired {
+        notifications.items.retain(|n| n.time < NOTIFICATION_TIME);
+    }
+}
+
+fn focus_notification_system(
+    mut cmds: Commands,
+    mut notifications: ResMut<Notifications>,
+    buildings: Query<&Building>,
+    lands: Query<&Pos>,
+) {
+    let Some(target) = notifications.bypass_change_detection().focus.take() else {
+        return;
+    };
+
+    // only for buildings, following every land notification would move the camera too often
+    let pos = buildings
+        .get(target)
+        .ok()
+        .and_then(|building| lands.get(building.land).ok());
+    if let Some(pos) = pos {
+        cmds.queue(focus_game_cam(pos.0, None));
+    }
+}
+
+fn update_notifications_ui_system(
+    mut cmds: Commands,
+    notifications: Res<Notifications>,
+    list: Single<Entity, With<NotificationList>>,
+    items: Query<Entity, With<NotificationItem>>,
+) {
+    if !notifications.is_changed() {
+        return;
+    }
+
+    let layout = UIGameLayout;
+    items.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+
+    let list = list.into_inner();
+    notifications.iter().for_each(|n| {
+        let item = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    NotificationItem,
+                    UIContainer {
+                        bg_color: Some(PICO8_BLACK),
+                        border_color: Some(n.color),
+                        border_size: 1.0,
+                    },
+                    UIStyle::default()
+                        .padding_left(4.0)
+                        .padding_right(4.0)
+                        .padding_y(2.0),
+                ),
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, list, item);
+
+        let txt = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    NotificationItem,
+                    UIText {
+                        text: n.text.clone(),
+                        color: n.color,
+                        size: 8.0,
+                        h_align: HAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, item, txt);
+    });
+}
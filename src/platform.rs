@@ -0,0 +1,135 @@
+// Seconds since the unix epoch
+pub fn now_secs() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Value passed as `--name value` in the command line
+#[cfg(not(target_arch = "wasm32"))]
+pub fn arg(name: &str) -> Option<String> {
+    let flag = format!("--{name}");
+    let mut args = std::env::args().skip_while(|a| *a != flag);
+    args.next()?;
+    args.next()
+}
+
+// Value passed as `?name=value` in the page url
+#[cfg(target_arch = "wasm32")]
+pub fn arg(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(k, v)| (k == name).then(|| v.to_string()))
+}
+
+// Persisted text stored with the name given
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_storage(name: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{name}.save")).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_storage(name: &str, value: &str) -> Result<(), String> {
+    std::fs::write(format!("{name}.save"), value).map_err(|e| e.to_string())
+}
+
+// The browser's local storage is used on the web
+#[cfg(target_arch = "wasm32")]
+pub fn load_storage(name: &str) -> Option<String> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item(name).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_storage(name: &str, value: &str) -> Result<(), String> {
+    let storage = web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| "Local storage is not available".to_string())?;
+    storage.set_item(name, value).map_err(|e| format!("{e:?}"))
+}
+
+// Touches are only read from the browser, native builds use the mouse
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_touch() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn take_touches() -> Vec<crate::input::TouchEvent> {
+    vec![]
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static TOUCHES: std::cell::RefCell<Vec<crate::input::TouchEvent>> =
+        const { std::cell::RefCell::new(vec![]) };
+}
+
+// Listens the touch events of the canvas, the default is prevented to avoid
+// the browser scrolling and emulating the mouse
+#[cfg(target_arch = "wasm32")]
+pub fn init_touch() {
+    use crate::input::{TouchEvent, TouchPhase};
+    use rkit::math::Vec2;
+    use wasm_bindgen::{JsCast, closure::Closure};
+
+    let Some(canvas) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.query_selector("canvas").ok().flatten())
+    else {
+        log::warn!("Canvas not found, touch input is disabled");
+        return;
+    };
+
+    let events = [
+        ("touchstart", TouchPhase::Start),
+        ("touchmove", TouchPhase::Move),
+        ("touchend", TouchPhase::End),
+        ("touchcancel", TouchPhase::Cancel),
+    ];
+
+    for (name, phase) in events {
+        let target = canvas.clone();
+        let callback =
+            Closure::<dyn FnMut(web_sys::TouchEvent)>::new(move |evt: web_sys::TouchEvent| {
+                evt.prevent_default();
+                let rect = target.get_bounding_client_rect();
+                let list = evt.changed_touches();
+                TOUCHES.with_borrow_mut(|touches| {
+                    (0..list.length())
+                        .filter_map(|i| list.get(i))
+                        .for_each(|touch| {
+                            touches.push(TouchEvent {
+                                id: touch.identifier() as u64,
+                                phase,
+                                pos: Vec2::new(
+                                    touch.client_x() as f32 - rect.left() as f32,
+                                    touch.client_y() as f32 - rect.top() as f32,
+                                ),
+                            })
+                        });
+                });
+            });
+
+        let options = web_sys::AddEventListenerOptions::new();
+        options.set_passive(false);
+        let res = canvas.add_event_listener_with_callback_and_add_event_listener_options(
+            name,
+            callback.as_ref().unchecked_ref(),
+            &options,
+        );
+        if let Err(err) = res {
+            log::warn!("Unable to listen '{name}': {err:?}");
+        }
+
+        // the listener lives as long as the page
+        callback.forget();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn take_touches() -> Vec<crate::input::TouchEvent> {
+    TOUCHES.with_borrow_mut(std::mem::take)
+}
@@ -0,0 +1,270 @@
+use rkit::{
+    math::{IVec2, UVec2, Vec2},
+    prelude::*,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    components::Pos,
+    consts::*,
+    economy::SimSet,
+    game::{BuildKind, Building, Land, Shutdown},
+    screens::AppScreen,
+    society::Society,
+    ui::{UIGameLayout, btns::create_text_btn, click::UIOnClick},
+};
+
+pub fn pollution_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_overlays_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::World.on_tick(
+                (
+                    emit_pollution_system,
+                    spread_pollution_system,
+                    spread_between_lands_system,
+                    pollution_happiness_system,
+                )
+                    .chain(),
+            ),
+        )
+        .add_screen_systems(screen, OnUpdate, toggle_overlay_key_system);
+}
+
+/// Extra layers drawn over the lands
+#[derive(Resource, Default)]
+pub struct Overlays {
+    pub pollution: bool,
+}
+
+impl BuildKind {
+    /// Pollution added to the tile each tick, negative values absorb it
+    pub fn pollution(&self) -> f32 {
+        match self {
+            BuildKind::Factory => 0.08,
+            BuildKind::Mine => 0.04,
+            BuildKind::Forest => -0.03,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Land {
+    /// Output multiplier for the buildings hurt by the pollution of the tile
+    pub fn pollution_multiplier(&self, kind: BuildKind, tile: UVec2) -> f32 {
+        match kind {
+            BuildKind::Farm | BuildKind::Forest => {
+                1.0 - self.pollution(tile) * POLLUTION_YIELD_PENALTY
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+fn neighbours(tile: UVec2) -> impl Iterator<Item = UVec2> {
+    let size = LAND_SIZE.as_ivec2();
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+        .into_iter()
+        .map(move |dir| tile.as_ivec2() + dir)
+        .filter(move |t| t.x >= 0 && t.y >= 0 && t.x < size.x && t.y < size.y)
+        .map(|t| t.as_uvec2())
+}
+
+// - systems
+fn init_overlays_system(mut cmds: Commands) {
+    cmds.insert_resource(Overlays::default());
+}
+
+fn emit_pollution_system(
+    mut lands: Query<&mut Land>,
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+) {
+    buildings.iter().for_each(|(kind, building)| {
+        let Ok(mut land) = lands.get_mut(building.land) else {
+            return;
+        };
+
+        let amount = kind.pollution() * building.level as f32;
+        if amount == 0.0 {
+            return;
+        }
+
+        // forests clean their neighbours too
+        let idx = Land::tile_index(building.pos);
+        land.pollution[idx] += amount;
+        if amount < 0.0 {
+            neighbours(building.pos).for_each(|tile| {
+                land.pollution[Land::tile_index(tile)] += amount * 0.5;
+            });
+        }
+    });
+}
+
+fn spread_pollution_system(mut lands: Query<&mut Land>) {
+    lands.iter_mut().for_each(|mut land| {
+        let UVec2 { x: cols, y: rows } = LAND_SIZE.as_uvec2();
+        let mut next = land.pollution.clone();
+        for y in 0..rows {
+            for x in 0..cols {
+                let tile = UVec2::new(x, y);
+                let flow = land.pollution(tile) * POLLUTION_SPREAD;
+                let targets = neighbours(tile).collect::<Vec<_>>();
+                next[Land::tile_index(tile)] -= flow;
+                targets.iter().for_each(|t| {
+                    next[Land::tile_index(*t)] += flow / targets.len() as f32;
+                });
+            }
+        }
+
+        land.pollution = next
+            .into_iter()
+            .map(|p| (p * (1.0 - POLLUTION_DECAY)).clamp(0.0, 1.0))
+            .collect();
+    });
+}
+
+fn spread_between_lands_system(mut lands: Query<(Entity, &mut Land, &Pos)>) {
+    let sources = lands
+        .iter()
+        .map(|(entity, land, pos)| {
+            let avg = land.pollution.iter().sum::<f32>() / land.pollution.len().max(1) as f32;
+            (entity, pos.0, avg)
+        })
+        .collect::<Vec<_>>();
+
+    let flows = land_flows(&sources);
+    lands.iter_mut().for_each(|(entity, mut land, _)| {
+        let Some(flow) = flows.get(&entity) else {
+            return;
+        };
+
+        land.pollution
+            .iter_mut()
+            .for_each(|p| *p = (*p * flow.keep + flow.incoming).clamp(0.0, 1.0));
+    });
+}
+
+/// Pollution moved between neighbouring lands on a tick
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LandFlow {
+    /// Factor that each tile keeps after sending its share to the neighbours
+    keep: f32,
+    /// Pollution added to each tile from the neighbours
+    incoming: f32,
+}
+
+/// Each land sends a share of its average pollution to every neighbour and
+/// loses the same amount, so the total only changes by the decay
+fn land_flows(sources: &[(Entity, Vec2, f32)]) -> FxHashMap<Entity, LandFlow> {
+    let max_dist = WORLD_CELL_SIZE.x.max(WORLD_CELL_SIZE.y) * 1.01;
+    sources
+        .iter()
+        .map(|&(entity, pos, _)| {
+            let (count, total) = sources
+                .iter()
+                .filter(|(e, p, _)| *e != entity && p.distance(pos) <= max_dist)
+                .fold((0, 0.0), |(n, total), (_, _, avg)| (n + 1, total + avg));
+
+            let flow = LandFlow {
+                keep: 1.0 - count as f32 * POLLUTION_LAND_SPREAD,
+                incoming: total * POLLUTION_LAND_SPREAD,
+            };
+            (entity, flow)
+        })
+        .collect()
+}
+
+fn pollution_happiness_system(
+    lands: Query<&Land>,
+    buildings: Query<(&BuildKind, &Building)>,
+    mut society: ResMut<Society>,
+) {
+    let (total, houses) = buildings
+        .iter()
+        .filter(|(kind, _)| matches!(kind, BuildKind::House))
+        .filter_map(|(_, b)| lands.get(b.land).ok().map(|land| land.pollution(b.pos)))
+        .fold((0.0, 0), |(total, n), p| (total + p, n + 1));
+
+    if houses == 0 {
+        society.pollution = 0.0;
+        return;
+    }
+
+    society.pollution = total / houses as f32;
+}
+
+// - overlay toggle
+pub fn create_pollution_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Pollution",
+        48.0,
+        UIOnClick::run(|In(_): In<Entity>, mut overlays: ResMut<Overlays>| {
+            overlays.pollution = !overlays.pollution;
+        }),
+    )
+}
+
+fn toggle_overlay_key_system(keyboard: Res<Keyboard>, mut overlays: ResMut<Overlays>) {
+    if keyboard.just_pressed(KeyCode::KeyP) {
+        overlays.pollution = !overlays.pollution;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pollution_moves_between_lands_without_growing() {
+        let lands = [
+            (Entity::from_raw(0), Vec2::ZERO, 1.0),
+            (Entity::from_raw(1), WORLD_CELL_SIZE * Vec2::X, 0.0),
+            (Entity::from_raw(2), WORLD_CELL_SIZE * Vec2::NEG_X, 0.5),
+        ];
+
+        let mut avgs = lands.map(|(_, _, avg)| avg);
+        let mut total: f32 = avgs.iter().sum();
+        (0..500).for_each(|_| {
+            let sources = lands
+                .iter()
+                .zip(avgs)
+                .map(|(&(e, pos, _), avg)| (e, pos, avg))
+                .collect::<Vec<_>>();
+
+            let flows = land_flows(&sources);
+            avgs = lands.map(|(e, _, _)| e).map(|e| {
+                let idx = e.index() as usize;
+                let flow = flows[&e];
+                (avgs[idx] * flow.keep + flow.incoming) * (1.0 - POLLUTION_DECAY)
+            });
+
+            let next: f32 = avgs.iter().sum();
+            assert!(
+                next <= total + 1e-6,
+                "pollution grew from {total} to {next}"
+            );
+            total = next;
+        });
+
+        assert!(total < 0.05, "pollution did not fade, {total} left");
+    }
+
+    #[test]
+    fn clean_neighbour_receives_what_the_source_loses() {
+        let sources = [
+            (Entity::from_raw(0), Vec2::ZERO, 1.0),
+            (Entity::from_raw(1), WORLD_CELL_SIZE * Vec2::Y, 0.0),
+            (Entity::from_raw(2), WORLD_CELL_SIZE * 3.0, 1.0),
+        ];
+
+        let flows = land_flows(&sources);
+        let source = flows[&sources[0].0];
+        let clean = flows[&sources[1].0];
+        assert!((1.0 - source.keep - clean.incoming).abs() < 1e-6);
+        assert_eq!(flows[&sources[2].0].keep, 1.0);
+    }
+}
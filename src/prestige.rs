@@ -0,0 +1,292 @@
+use rkit::prelude::*;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    bank::Bank,
+    consts::*,
+    economy::Money,
+    notify::Notifications,
+    save::SaveFile,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::{UITextButton, create_text_btn},
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+};
+
+const SAVE_SECTION: &str = "prestige";
+
+pub fn prestige_plugin(app: &mut App) {
+    app.add_resource(Prestige::load()).add_screen_systems(
+        AppScreen::Game,
+        OnUpdate,
+        update_prestige_panel_system,
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum PrestigeUpgrade {
+    /// Production multiplier for every building
+    Yield,
+    /// Extra money at the start of each depth
+    Funds,
+}
+
+impl PrestigeUpgrade {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrestigeUpgrade::Yield => "Yield",
+            PrestigeUpgrade::Funds => "Funds",
+        }
+    }
+
+    /// Shards needed to reach the next level
+    pub fn cost(&self, level: u32) -> u32 {
+        let base = match self {
+            PrestigeUpgrade::Yield => 2,
+            PrestigeUpgrade::Funds => 1,
+        };
+        base * (level + 1)
+    }
+}
+
+/// Progress kept between resets, each reset goes one level deeper
+#[derive(Resource, Default, Debug, Clone)]
+pub struct Prestige {
+    pub depth: u32,
+    pub shards: u32,
+    pub yield_level: u32,
+    pub funds_level: u32,
+}
+
+impl Prestige {
+    fn load() -> Self {
+        let file = SaveFile::load();
+        let get = |key| file.get(SAVE_SECTION, key).unwrap_or_default();
+        Self {
+            depth: get("depth"),
+            shards: get("shards"),
+            yield_level: get("yield_level"),
+            funds_level: get("funds_level"),
+        }
+    }
+
+    fn store(&self) {
+        SaveFile::update_section(
+            SAVE_SECTION,
+            &[
+                ("depth", self.depth.to_string()),
+                ("shards", self.shards.to_string()),
+                ("yield_level", self.yield_level.to_string()),
+                ("funds_level", self.funds_level.to_string()),
+            ],
+        );
+    }
+
+    pub fn level(&self, upgrade: PrestigeUpgrade) -> u32 {
+        match upgrade {
+            PrestigeUpgrade::Yield => self.yield_level,
+            PrestigeUpgrade::Funds => self.funds_level,
+        }
+    }
+
+    pub fn production_multiplier(&self) -> f32 {
+        1.0 + self.yield_level as f32 * PRESTIGE_YIELD_BONUS
+    }
+
+    pub fn starting_money(&self) -> f32 {
+        STARTING_MONEY + self.funds_level as f32 * PRESTIGE_FUNDS_BONUS
+    }
+
+    fn buy(&mut self, upgrade: PrestigeUpgrade) -> bool {
+        let cost = upgrade.cost(self.level(upgrade));
+        if self.shards < cost {
+            return false;
+        }
+
+        self.shards -= cost;
+        match upgrade {
+            PrestigeUpgrade::Yield => self.yield_level += 1,
+            PrestigeUpgrade::Funds => self.funds_level += 1,
+        }
+        self.store();
+        true
+    }
+}
+
+/// Shards earned going deeper with this money, zero before the milestone
+pub fn prestige_reward(net_worth: f32) -> u32 {
+    if net_worth < PRESTIGE_MILESTONE {
+        return 0;
+    }
+
+    (net_worth / PRESTIGE_MILESTONE).sqrt().floor() as u32
+}
+
+/// Shards for going deeper, the loans have to be repaid first so borrowed
+/// money never counts and the debt is not left behind
+fn deeper_reward(money: &Money, bank: &Bank) -> Option<u32> {
+    if !bank.loans.is_empty() {
+        return None;
+    }
+
+    Some(prestige_reward(money.0 - bank.debt()))
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct PrestigePanel;
+
+#[derive(Component, Clone, Copy)]
+struct PrestigeText;
+
+#[derive(Component, Clone, Copy)]
+struct UpgradeText(PrestigeUpgrade);
+
+#[derive(Component, Clone, Copy)]
+struct UpgradeBtn(PrestigeUpgrade);
+
+#[derive(Component, Clone, Copy)]
+struct DeeperBtn;
+
+pub fn create_prestige_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Depth",
+        48.0,
+        UIOnClick::run(toggle_prestige_panel_system),
+    )
+}
+
+fn toggle_prestige_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<PrestigePanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Depth", PrestigePanel);
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (PrestigeText, panel_text("", PICO8_WHITE)),
+        );
+
+        PrestigeUpgrade::iter().for_each(|upgrade| {
+            let row = spawn_panel_node(cmds, panel, content, panel_row());
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (UpgradeText(upgrade), panel_text("", PICO8_LIGHT_GRAY)),
+            );
+            let btn = create_text_btn(
+                cmds,
+                UIGameLayout,
+                "Buy",
+                32.0,
+                (
+                    PanelNode(panel),
+                    UpgradeBtn(upgrade),
+                    UIOnClick::run(buy_upgrade_system),
+                ),
+            );
+            cmds.add_ui_child(UIGameLayout, row, btn);
+        });
+
+        let btn = create_text_btn(
+            cmds,
+            UIGameLayout,
+            "Go deeper",
+            64.0,
+            (
+                PanelNode(panel),
+                DeeperBtn,
+                UIOnClick::run(go_deeper_system),
+            ),
+        );
+        cmds.add_ui_child(UIGameLayout, content, btn);
+    });
+}
+
+fn buy_upgrade_system(
+    In(entity): In<Entity>,
+    btns: Query<&UpgradeBtn>,
+    mut prestige: ResMut<Prestige>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    if !prestige.buy(btn.0) {
+        notifications.push("Not enough shards", PICO8_ORANGE, None);
+    }
+}
+
+fn go_deeper_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    mut prestige: ResMut<Prestige>,
+    money: Res<Money>,
+    bank: Res<Bank>,
+) {
+    let reward = deeper_reward(&money, &bank).unwrap_or(0);
+    if reward == 0 {
+        return;
+    }
+
+    prestige.depth += 1;
+    prestige.shards += reward;
+    prestige.store();
+    cmds.queue(ChangeScreen(AppScreen::Deeper));
+}
+
+fn update_prestige_panel_system(
+    mut info_text: Query<&mut UIText, With<PrestigeText>>,
+    mut upgrade_texts: Query<(&mut UIText, &UpgradeText), Without<PrestigeText>>,
+    mut upgrade_btns: Query<(&mut UITextButton, &UpgradeBtn), Without<DeeperBtn>>,
+    mut deeper_btn: Query<&mut UITextButton, With<DeeperBtn>>,
+    prestige: Res<Prestige>,
+    money: Res<Money>,
+    bank: Res<Bank>,
+) {
+    let reward = deeper_reward(&money, &bank);
+    info_text.iter_mut().for_each(|mut text| {
+        let (depth, shards) = (prestige.depth, prestige.shards);
+        text.text = match reward {
+            None => format!("Depth {depth} - {shards} shards, repay the loans to go deeper"),
+            Some(0) => format!(
+                "Depth {depth} - {shards} shards, reach ${PRESTIGE_MILESTONE:.0} to go deeper"
+            ),
+            Some(reward) => format!("Depth {depth} - {shards} shards, +{reward} going deeper"),
+        };
+    });
+
+    upgrade_texts.iter_mut().for_each(|(mut text, upgrade)| {
+        let level = prestige.level(upgrade.0);
+        text.text = format!(
+            "{} lvl {level} ({} shards)",
+            upgrade.0.name(),
+            upgrade.0.cost(level)
+        );
+    });
+
+    upgrade_btns.iter_mut().for_each(|(mut btn, upgrade)| {
+        btn.enabled = prestige.shards >= upgrade.0.cost(prestige.level(upgrade.0));
+    });
+
+    deeper_btn
+        .iter_mut()
+        .for_each(|mut btn| btn.enabled = reward.is_some_and(|r| r > 0));
+}
@@ -8,11 +8,15 @@ use rkit::{
 
 use crate::{
     assets::Assets,
+    calendar::Calendar,
     camera::{Cam, GameCam, UICam},
     components::Pos,
     consts::*,
-    game::{BuildKind, Building, Land},
+    game::{BuildKind, Building, Land, Shutdown},
+    logistics::{Route, Shipment},
+    pollution::Overlays,
     postfx::rtf,
+    rivals::Rival,
     screens::AppScreen,
     ui::UIGameLayout,
 };
@@ -38,7 +42,12 @@ fn init_resources_system(mut cmds: Commands) {
 
 // generate the render systems pipeline
 fn create_render_wrapper(cmds: &mut Commands) -> RenderSysFn {
-    let mut systems = vec![cmds.register_system(draw_land_layer_system)];
+    let mut systems = vec![
+        cmds.register_system(draw_land_layer_system),
+        cmds.register_system(draw_pollution_layer_system),
+        cmds.register_system(draw_excavation_layer_system),
+        cmds.register_system(draw_transit_layer_system),
+    ];
 
     #[cfg(debug_assertions)]
     {
@@ -81,9 +90,11 @@ fn draw_ui_system(world: &mut World) {
 fn draw_land_layer_system(
     mut draw: InMut<Draw2D>,
     lands: Query<(&Land, &Pos)>,
-    buildings: Query<(&BuildKind, &Building)>,
+    buildings: Query<(&BuildKind, &Building, Has<Shutdown>)>,
+    rivals: Query<&Rival>,
     cam: Single<&Cam, With<GameCam>>,
     assets: Res<Assets>,
+    calendar: Res<Calendar>,
 ) {
     draw.set_camera(cam.into_inner().deref());
     draw.clear(PICO8_BLACK);
@@ -95,10 +106,15 @@ fn draw_land_layer_system(
         // outline
         let is_hover = land.hover.is_some();
         let stroke_width = 2.0;
+        let rival = land.rival.and_then(|e| rivals.get(e).ok());
         let stroke_color = if is_hover {
             PICO8_INDIGO
-        } else {
+        } else if let Some(rival) = rival {
+            rival.color
+        } else if land.owned {
             PICO8_DARK_GRAY
+        } else {
+            PICO8_DARK_BLUE
         };
         draw.rect(
             Vec2::ZERO, // - LAND_GAP - stroke_width * 0.5,
@@ -110,6 +126,14 @@ fn draw_land_layer_system(
         .stroke_color(stroke_color)
         .stroke(stroke_width);
 
+        // seasonal tint
+        draw.rect(Vec2::ZERO, LAND_SIZE * tile_with_gap)
+            .origin(Vec2::splat(0.5))
+            .translate(relative_pos - TILE_GAP * 0.5)
+            .fill_color(calendar.season.tint())
+            .alpha(0.05)
+            .fill();
+
         // inner grid
         let UVec2 { x: cols, y: rows } = LAND_SIZE.as_uvec2();
         for y in 0..rows {
@@ -119,6 +143,12 @@ fn draw_land_layer_system(
                 let tile_pos =
                     relative_pos + (tile_f32 * tile_with_gap - (LAND_SIZE * tile_with_gap * 0.5));
 
+                // terrain background
+                draw.image(&assets.white_square)
+                    .translate(tile_pos)
+                    .color(land.terrain(tile).color())
+                    .alpha(if land.owned { 0.08 } else { 0.04 });
+
                 draw.image(&assets.dotted_square)
                     .translate(tile_pos)
                     .alpha(0.02);
@@ -141,26 +171,60 @@ fn draw_land_layer_system(
             }
         }
 
+        // price tag for the lands available to buy, or the owner if a rival took it
+        if let Some(rival) = rival {
+            draw.text(&rival.profile.name)
+                .origin(Vec2::splat(0.5))
+                .translate(relative_pos)
+                .color(rival.color)
+                .size(8.0);
+        } else if !land.owned {
+            draw.text(&format!("${:.0}", land.price))
+                .origin(Vec2::splat(0.5))
+                .translate(relative_pos)
+                .color(PICO8_LIGHT_GRAY)
+                .size(8.0);
+        }
+
         // draw buildings
         land.buildings.iter().for_each(|entity| {
-            let Ok((kind, building)) = buildings.get(*entity) else {
+            let Ok((kind, building, is_shutdown)) = buildings.get(*entity) else {
                 return;
             };
 
             let tile_f32 = building.pos.as_vec2();
             let tile_pos =
                 relative_pos + (tile_f32 * tile_with_gap - (LAND_SIZE * tile_with_gap * 0.5));
-            let img = match kind {
-                BuildKind::Farm => &assets.farm,
-                BuildKind::House => &assets.house,
-                BuildKind::Forest => &assets.forest,
-                BuildKind::Factory => &assets.factory,
-                BuildKind::Shop => &assets.shop,
-                BuildKind::Mine => &assets.mine,
-            };
-            draw.image(img).translate(tile_pos);
+            let alpha = if is_shutdown { 0.3 } else { 1.0 };
+            draw.image(assets.building(*kind))
+                .translate(tile_pos)
+                .alpha(alpha);
+
+            // level indicator for upgraded buildings
+            if building.level > 1 {
+                draw.text(&building.level.to_string())
+                    .translate(tile_pos + Vec2::splat(TILE_SIZE - 1.0))
+                    .origin(Vec2::ONE)
+                    .color(PICO8_YELLOW)
+                    .size(6.0);
+            }
         });
 
+        // rival buildings are drawn faded with the color of the company
+        if let Some(rival) = rival {
+            land.rival_buildings.iter().for_each(|(tile, kind)| {
+                let tile_pos = relative_pos
+                    + (tile.as_vec2() * tile_with_gap - (LAND_SIZE * tile_with_gap * 0.5));
+                draw.image(assets.building(*kind))
+                    .translate(tile_pos)
+                    .alpha(0.6);
+                draw.rect(Vec2::ZERO, Vec2::splat(3.0))
+                    .translate(tile_pos)
+                    .fill_color(rival.color)
+                    .fill();
+            });
+        }
+
         // draw overlay
         for y in 0..rows {
             for x in 0..cols {
@@ -188,3 +252,149 @@ fn draw_land_layer_system(
         }
     });
 }
+
+fn draw_pollution_layer_system(
+    mut draw: InMut<Draw2D>,
+    lands: Query<(&Land, &Pos)>,
+    overlays: Res<Overlays>,
+    assets: Res<Assets>,
+) {
+    if !overlays.pollution {
+        return;
+    }
+
+    let tile_with_gap = TILE_SIZE + TILE_GAP;
+    lands.iter().for_each(|(land, pos)| {
+        let relative_pos = pos.0 - LAND_GAP;
+        let UVec2 { x: cols, y: rows } = LAND_SIZE.as_uvec2();
+        for y in 0..rows {
+            for x in 0..cols {
+                let tile = UVec2::new(x, y);
+                let pollution = land.pollution(tile);
+                if pollution <= 0.01 {
+                    continue;
+                }
+
+                let tile_pos = relative_pos
+                    + (tile.as_vec2() * tile_with_gap - (LAND_SIZE * tile_with_gap * 0.5));
+                let color = if pollution > 0.5 {
+                    PICO8_RED
+                } else {
+                    PICO8_ORANGE
+                };
+
+                draw.image(&assets.white_square)
+                    .translate(tile_pos)
+                    .color(color)
+                    .alpha(0.2 + pollution * 0.5);
+            }
+        }
+    });
+}
+
+fn draw_transit_layer_system(
+    mut draw: InMut<Draw2D>,
+    routes: Query<&Route>,
+    lands: Query<&Pos, With<Land>>,
+    shipments: Query<&Shipment>,
+) {
+    routes.iter().for_each(|route| {
+        let (Ok(from), Ok(to)) = (lands.get(route.from), lands.get(route.to)) else {
+            return;
+        };
+
+        draw.line(from.0, to.0)
+            .color(PICO8_DARK_GRAY)
+            .alpha(0.5)
+            .width(1.0);
+    });
+
+    // goods in transit
+    shipments.iter().for_each(|shipment| {
+        draw.rect(Vec2::ZERO, Vec2::splat(3.0))
+            .origin(Vec2::splat(0.5))
+            .translate(shipment.position())
+            .fill_color(PICO8_PEACH)
+            .fill();
+    });
+}
+
+fn draw_excavation_layer_system(
+    mut draw: InMut<Draw2D>,
+    lands: Query<(&Land, &Pos)>,
+    assets: Res<Assets>,
+) {
+    let tile_with_gap = TILE_SIZE + TILE_GAP;
+    lands
+        .iter()
+        .filter(|(land, _)| land.owned)
+        .for_each(|(land, pos)| {
+            let relative_pos = pos.0 - LAND_GAP;
+            let UVec2 { x: cols, y: rows } = LAND_SIZE.as_uvec2();
+            for y in 0..rows {
+                for x in 0..cols {
+                    let tile = UVec2::new(x, y);
+                    let excavation = land.excavation(tile);
+                    if excavation.depth == 0 && !excavation.is_digging() {
+                        continue;
+                    }
+
+                    let tile_pos = relative_pos
+                        + (tile.as_vec2() * tile_with_gap - (LAND_SIZE * tile_with_gap * 0.5));
+
+                    if excavation.abandoned {
+                        draw.image(&assets.white_square)
+                            .translate(tile_pos)
+                            .color(PICO8_BLACK)
+                            .alpha(0.6);
+                        continue;
+                    }
+
+                    // small icon of the ore found
+                    if let Some(deposit) = excavation.deposit {
+                        let alpha = if deposit.is_depleted() { 0.3 } else { 1.0 };
+                        draw.image(assets.resource(deposit.ore))
+                            .translate(tile_pos)
+                            .scale(Vec2::splat(0.5))
+                            .alpha(alpha);
+                    }
+
+                    // hazards tint the whole tile
+                    if let Some(hazard) = excavation.hazard {
+                        draw.image(&assets.white_square)
+                            .translate(tile_pos)
+                            .color(hazard.color())
+                            .alpha(0.4);
+                    }
+
+                    // safety installed, supports show their level
+                    let mut safety = String::new();
+                    if excavation.pump {
+                        safety.push('P');
+                    }
+                    if excavation.ventilation {
+                        safety.push('V');
+                    }
+                    if excavation.supports > 0 {
+                        safety.push_str(&excavation.supports.to_string());
+                    }
+                    if !safety.is_empty() {
+                        draw.text(&safety)
+                            .translate(tile_pos + Vec2::ONE)
+                            .color(PICO8_LIGHT_GRAY)
+                            .size(6.0);
+                    }
+
+                    // progress bar while digging
+                    if let Some(progress) = excavation.progress {
+                        let bar_pos = tile_pos + Vec2::new(1.0, TILE_SIZE - 3.0);
+                        let bar_size = Vec2::new(TILE_SIZE - 2.0, 2.0);
+                        draw.rect(bar_pos, bar_size).fill_color(PICO8_BLACK).fill();
+                        draw.rect(bar_pos, bar_size * Vec2::new(progress, 1.0))
+                            .fill_color(PICO8_YELLOW)
+                            .fill();
+                    }
+                }
+            }
+        });
+}
@@ -0,0 +1,517 @@
+use rkit::{
+    gfx::Color,
+    math::{UVec2, uvec2},
+    prelude::*,
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+    assets::Assets,
+    bank::Bank,
+    consts::*,
+    economy::{Market, Money, SimSet, TotalStock},
+    game::{BuildKind, Land, ResourceKind},
+    inflation::Inflation,
+    notify::Notifications,
+    save::SaveFile,
+    screens::AppScreen,
+    seed::{GameSeed, SeedRng},
+    ui::{
+        UIGameLayout,
+        btns::create_text_btn,
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+};
+
+// Salt to keep the rivals decisions independent of the world generation
+const RIVALS_SALT: u64 = 0x21_7A15;
+
+const RIVAL_COLORS: [Color; 4] = [PICO8_RED, PICO8_BLUE, PICO8_GREEN, PICO8_PINK];
+
+pub fn rivals_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_rivals_system)
+        .add_systems(OnExit(screen), cleanup_rivals_system)
+        .add_screen_systems(screen, OnUpdate, SimSet::Trade.on_tick(rivals_system))
+        .add_screen_systems(screen, OnUpdate, update_leaderboard_system);
+}
+
+/// Personality of a rival, loaded from `assets/data/rivals.ini`
+#[derive(Clone, Debug)]
+pub struct RivalProfile {
+    pub name: String,
+    /// Resource that the company produces and sells
+    pub focus: ResourceKind,
+    /// Chance each tick of buying a land
+    pub aggression: f32,
+    /// Chance each tick of constructing a building on its lands
+    pub builder: f32,
+    /// Part of the stock sold each tick
+    pub greed: f32,
+    /// Units produced per land each tick
+    pub growth: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RivalProfiles(pub Vec<RivalProfile>);
+
+pub fn parse_rivals(file: &SaveFile) -> Result<RivalProfiles, String> {
+    let profiles = file
+        .section_names()
+        .map(|name| {
+            let focus = file
+                .get::<String>(name, "focus")
+                .and_then(|focus| ResourceKind::from_name(&focus))
+                .ok_or_else(|| format!("Invalid focus for rival '{name}'"))?;
+
+            Ok(RivalProfile {
+                name: name.to_string(),
+                focus,
+                aggression: file.get(name, "aggression").unwrap_or(0.02),
+                builder: file.get(name, "builder").unwrap_or(0.05),
+                greed: file.get(name, "greed").unwrap_or(0.5),
+                growth: file.get(name, "growth").unwrap_or(1.0),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(RivalProfiles(profiles))
+}
+
+/// Simulated company, it owns a headquarter outside the map and
+/// buys lands from it to grow
+#[derive(Component, Clone, Debug)]
+pub struct Rival {
+    pub profile: RivalProfile,
+    pub color: Color,
+    pub money: f32,
+    pub stock: f32,
+    pub lands: u32,
+}
+
+impl Rival {
+    /// Same valuation as the player, `land_value` is the value of its lands
+    pub fn net_worth(&self, market: &Market, land_value: f32) -> f32 {
+        self.money + self.stock * market.price(self.profile.focus) + land_value
+    }
+
+    /// Building that produces the resource of the company
+    pub fn build_kind(&self) -> BuildKind {
+        match self.profile.focus {
+            ResourceKind::Woord => BuildKind::Forest,
+            ResourceKind::Food => BuildKind::Farm,
+            ResourceKind::People => BuildKind::House,
+            ResourceKind::Ring => BuildKind::Factory,
+            _ => BuildKind::Mine,
+        }
+    }
+}
+
+/// Value of an owned land for the net worth, the same for everyone
+pub fn land_value(land: &Land) -> f32 {
+    land.price.max(LAND_BASE_PRICE)
+}
+
+/// Best free tile of the rival lands for the building, the terrain
+/// that multiplies its output first
+fn find_build_tile<'a>(
+    lands: impl Iterator<Item = (Entity, &'a Land)>,
+    rival: Entity,
+    kind: BuildKind,
+) -> Option<(Entity, UVec2)> {
+    let UVec2 { x: cols, y: rows } = LAND_SIZE.as_uvec2();
+    lands
+        .filter(|(_, land)| land.rival == Some(rival))
+        .flat_map(|(entity, land)| {
+            (0..rows)
+                .flat_map(move |y| (0..cols).map(move |x| uvec2(x, y)))
+                .filter(|tile| land.rival_buildings.iter().all(|(t, _)| t != tile))
+                .map(move |tile| (entity, tile, land.terrain(tile).multiplier(kind)))
+        })
+        // first the best terrain, then the lowest entity and tile
+        .min_by(|(ea, ta, ma), (eb, tb, mb)| {
+            mb.total_cmp(ma)
+                .then(ea.cmp(eb))
+                .then((ta.y, ta.x).cmp(&(tb.y, tb.x)))
+        })
+        .map(|(entity, tile, _)| (entity, tile))
+}
+
+/// Money and goods of the player minus the debt with the bank
+pub fn player_net_worth(
+    money: f32,
+    stock: &TotalStock,
+    market: &Market,
+    lands: f32,
+    bank: &Bank,
+) -> f32 {
+    let goods: f32 = ResourceKind::iter()
+        .filter(|kind| kind.is_tradable())
+        .map(|kind| stock.get(kind) * market.price(kind))
+        .sum();
+    money + goods + lands - bank.debt()
+}
+
+#[derive(Resource)]
+struct RivalsRng(SeedRng);
+
+// - systems
+fn init_rivals_system(mut cmds: Commands, assets: Res<Assets>, seed: Res<GameSeed>) {
+    cmds.insert_resource(RivalsRng(SeedRng::fork(seed.value, RIVALS_SALT)));
+    assets
+        .rivals
+        .0
+        .iter()
+        .enumerate()
+        .for_each(|(idx, profile)| {
+            cmds.spawn(Rival {
+                profile: profile.clone(),
+                color: RIVAL_COLORS[idx % RIVAL_COLORS.len()],
+                money: RIVAL_STARTING_MONEY,
+                stock: 0.0,
+                lands: 1,
+            });
+        });
+}
+
+fn cleanup_rivals_system(mut cmds: Commands, query: Query<Entity, With<Rival>>) {
+    query.iter().for_each(|e| cmds.entity(e).despawn());
+}
+
+fn rivals_system(
+    mut rivals: Query<(Entity, &mut Rival)>,
+    mut lands: Query<(Entity, &mut Land)>,
+    mut market: ResMut<Market>,
+    mut rng: ResMut<RivalsRng>,
+    mut notifications: ResMut<Notifications>,
+    inflation: Res<Inflation>,
+) {
+    // sorted to keep the decisions the same for the same seed
+    let mut order = rivals.iter().map(|(e, _)| e).collect::<Vec<_>>();
+    order.sort();
+
+    order.into_iter().for_each(|entity| {
+        let Ok((_, mut rival)) = rivals.get_mut(entity) else {
+            return;
+        };
+
+        // the headquarter and the lands produce a bit, the buildings the most
+        let focus = rival.profile.focus;
+        let kind = rival.build_kind();
+        let built: f32 = lands
+            .iter()
+            .filter(|(_, land)| land.rival == Some(entity))
+            .flat_map(|(_, land)| {
+                land.rival_buildings
+                    .iter()
+                    .map(|(tile, k)| land.terrain(*tile).multiplier(*k))
+            })
+            .sum();
+        rival.stock += (rival.lands as f32 + built) * rival.profile.growth;
+        rival.money -= rival.lands as f32 * RIVAL_UPKEEP;
+
+        // they sell on the same market, pushing the prices down
+        let min_price = market.base_price(focus) * RIVAL_MIN_PRICE_FACTOR;
+        if market.price(focus) >= min_price {
+            let amount = (rival.stock * rival.profile.greed).floor();
+            if amount > 0.0 {
+                rival.stock -= amount;
+                rival.money += market.sell(focus, amount);
+            }
+        }
+
+        // in debt they sell back their cheapest land, the headquarter can't be sold
+        // so without lands left the debt is forgiven
+        if rival.money < 0.0 {
+            let sold = lands
+                .iter_mut()
+                .filter(|(_, land)| land.rival == Some(entity))
+                .min_by(|(ea, a), (eb, b)| a.price.total_cmp(&b.price).then(ea.cmp(eb)));
+            if let Some((land_e, mut land)) = sold {
+                land.rival = None;
+                land.rival_buildings.clear();
+                rival.money += land.price;
+                rival.lands -= 1;
+                notifications.push(
+                    format!("{} sold a land to pay its debts", rival.profile.name),
+                    rival.color,
+                    Some(land_e),
+                );
+            }
+
+            rival.money = rival.money.max(0.0);
+            return;
+        }
+
+        // they build on their lands with the same costs as the player
+        let cost = kind.cost(inflation.index);
+        let builds = rng.0.chance(rival.profile.builder) && cost <= rival.money * RIVAL_LAND_BUDGET;
+        let site = builds
+            .then(|| find_build_tile(lands.iter(), entity, kind))
+            .flatten()
+            .and_then(|(land_e, tile)| Some((land_e, lands.get_mut(land_e).ok()?.1, tile)));
+        if let Some((land_e, mut land, tile)) = site {
+            land.rival_buildings.push((tile, kind));
+            rival.money -= cost;
+            notifications.push(
+                format!("{} built a {}", rival.profile.name, kind.name()),
+                rival.color,
+                Some(land_e),
+            );
+        }
+
+        if !rng.0.chance(rival.profile.aggression) {
+            return;
+        }
+
+        // cheapest land available that fits the budget
+        let budget = rival.money * RIVAL_LAND_BUDGET;
+        let land = lands
+            .iter_mut()
+            .filter(|(_, land)| !land.owned && land.rival.is_none() && land.price <= budget)
+            .min_by(|(ea, a), (eb, b)| a.price.total_cmp(&b.price).then(ea.cmp(eb)));
+
+        if let Some((land_e, mut land)) = land {
+            land.rival = Some(entity);
+            rival.money -= land.price;
+            rival.lands += 1;
+            notifications.push(
+                format!("{} bought a land", rival.profile.name),
+                rival.color,
+                Some(land_e),
+            );
+        }
+    });
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct LeaderboardPanel;
+
+#[derive(Component, Clone, Copy)]
+struct LeaderboardRow(usize);
+
+pub fn create_leaderboard_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Rivals",
+        48.0,
+        UIOnClick::run(toggle_leaderboard_panel_system),
+    )
+}
+
+fn toggle_leaderboard_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<LeaderboardPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+    rivals: Query<(), With<Rival>>,
+) {
+    let container = container.into_inner();
+    let count = rivals.iter().count() + 1;
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Net worth", LeaderboardPanel);
+        (0..count).for_each(|idx| {
+            let row = spawn_panel_node(cmds, panel, content, panel_row());
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (LeaderboardRow(idx), panel_text("", PICO8_WHITE)),
+            );
+        });
+    });
+}
+
+fn update_leaderboard_system(
+    mut rows: Query<(&mut UIText, &LeaderboardRow)>,
+    rivals: Query<(Entity, &Rival)>,
+    lands: Query<&Land>,
+    stock: Res<TotalStock>,
+    market: Res<Market>,
+    money: Res<Money>,
+    bank: Res<Bank>,
+) {
+    if rows.is_empty() {
+        return;
+    }
+
+    let player_lands = lands.iter().filter(|land| land.owned).map(land_value).sum();
+    let mut board = vec![(
+        "You".to_string(),
+        player_net_worth(money.0, &stock, &market, player_lands, &bank),
+        PICO8_YELLOW,
+    )];
+    board.extend(rivals.iter().map(|(entity, r)| {
+        // the headquarter outside the map is worth a base land
+        let rival_lands: f32 = lands
+            .iter()
+            .filter(|land| land.rival == Some(entity))
+            .map(land_value)
+            .sum();
+        let worth = r.net_worth(&market, LAND_BASE_PRICE + rival_lands);
+        (r.profile.name.clone(), worth, r.color)
+    }));
+    board.sort_by(|(_, a, _), (_, b, _)| b.total_cmp(a));
+
+    rows.iter_mut().for_each(|(mut text, row)| {
+        let Some((name, worth, color)) = board.get(row.0) else {
+            return;
+        };
+
+        text.text = format!("{}. {name}: ${worth:.0}", row.0 + 1);
+        text.color = *color;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::ecs::bevy_ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::terrain::Terrain;
+
+    fn rival_land(rival: Entity, terrain: Terrain) -> Land {
+        let tiles = LAND_SIZE.x as usize * LAND_SIZE.y as usize;
+        Land {
+            rival: Some(rival),
+            terrain: vec![terrain; tiles],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rivals_build_on_the_best_free_tile() {
+        let rival = Entity::from_raw(10);
+        let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+        let plain = rival_land(rival, Terrain::Plain);
+        let mut fertile = rival_land(rival, Terrain::Plain);
+        fertile.terrain[4] = Terrain::Fertile;
+
+        let lands = [(a, &plain), (b, &fertile)];
+        let site = find_build_tile(lands.into_iter(), rival, BuildKind::Farm);
+        assert_eq!(site, Some((b, uvec2(1, 1))));
+
+        // once taken it goes to the first free tile of the first land
+        fertile.rival_buildings.push((uvec2(1, 1), BuildKind::Farm));
+        let lands = [(b, &fertile), (a, &plain)];
+        let site = find_build_tile(lands.into_iter(), rival, BuildKind::Farm);
+        assert_eq!(site, Some((a, uvec2(0, 0))));
+    }
+
+    #[test]
+    fn rivals_only_build_on_their_lands() {
+        let rival = Entity::from_raw(10);
+        let other = rival_land(Entity::from_raw(11), Terrain::Fertile);
+        let lands = [(Entity::from_raw(1), &other)];
+        let site = find_build_tile(lands.into_iter(), rival, BuildKind::Farm);
+        assert_eq!(site, None);
+    }
+
+    #[test]
+    fn lands_are_valued_the_same_for_everyone() {
+        let cheap = Land {
+            price: 10.0,
+            ..Default::default()
+        };
+        let expensive = Land {
+            price: 400.0,
+            ..Default::default()
+        };
+        assert_eq!(land_value(&cheap), LAND_BASE_PRICE);
+        assert_eq!(land_value(&expensive), 400.0);
+    }
+
+    #[test]
+    fn missing_values_use_the_defaults() {
+        let file = SaveFile::parse("[Acme]\nfocus=Copper\ngreed=0.9\n");
+        let profiles = parse_rivals(&file).unwrap();
+        let [acme] = profiles.0.as_slice() else {
+            panic!("expected one rival");
+        };
+        assert_eq!(acme.name, "Acme");
+        assert_eq!(acme.focus, ResourceKind::Copper);
+        assert_eq!(acme.greed, 0.9);
+        assert_eq!(acme.aggression, 0.02);
+        assert_eq!(acme.builder, 0.05);
+        assert_eq!(acme.growth, 1.0);
+    }
+
+    #[test]
+    fn unknown_or_missing_focus_is_an_error() {
+        let bad = SaveFile::parse("[Acme]\nfocus=Diamonds\n");
+        let err = parse_rivals(&bad).unwrap_err();
+        assert_eq!(err, "Invalid focus for rival 'Acme'");
+
+        let missing = SaveFile::parse("[Acme]\ngreed=0.5\n");
+        assert!(parse_rivals(&missing).is_err());
+    }
+
+    fn idle_rival(money: f32, lands: u32) -> Rival {
+        Rival {
+            profile: RivalProfile {
+                name: "Acme".to_string(),
+                focus: ResourceKind::Copper,
+                aggression: 1.0,
+                builder: 1.0,
+                greed: 0.0,
+                growth: 0.0,
+            },
+            color: PICO8_RED,
+            money,
+            stock: 0.0,
+            lands,
+        }
+    }
+
+    fn rivals_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(Market::default());
+        world.insert_resource(RivalsRng(SeedRng::new(1)));
+        world.insert_resource(Notifications::default());
+        world.insert_resource(Inflation::default());
+        world
+    }
+
+    #[test]
+    fn rivals_in_debt_sell_their_cheapest_land() {
+        let mut world = rivals_world();
+        let rival = world.spawn(idle_rival(0.0, 3)).id();
+        let mut land = |price: f32| {
+            let mut land = rival_land(rival, Terrain::Plain);
+            land.price = price;
+            land.rival_buildings.push((uvec2(0, 0), BuildKind::Mine));
+            world.spawn(land).id()
+        };
+        let (expensive, cheap) = (land(80.0), land(50.0));
+
+        world.run_system_once(rivals_system).unwrap();
+
+        // the upkeep of 3 lands is paid with the sale, nothing else is bought
+        let r = world.get::<Rival>(rival).unwrap();
+        assert_eq!(r.lands, 2);
+        assert_eq!(r.money, 50.0 - 3.0 * RIVAL_UPKEEP);
+        let sold = world.get::<Land>(cheap).unwrap();
+        assert_eq!(sold.rival, None);
+        assert!(sold.rival_buildings.is_empty());
+        assert_eq!(world.get::<Land>(expensive).unwrap().rival, Some(rival));
+    }
+
+    #[test]
+    fn rivals_without_lands_never_go_below_zero() {
+        let mut world = rivals_world();
+        let rival = world.spawn(idle_rival(0.2, 1)).id();
+
+        (0..10).for_each(|_| world.run_system_once(rivals_system).unwrap());
+
+        let r = world.get::<Rival>(rival).unwrap();
+        assert_eq!(r.money, 0.0);
+        assert_eq!(r.lands, 1);
+    }
+}
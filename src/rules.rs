@@ -0,0 +1,598 @@
+use rkit::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    camera::CamShake,
+    consts::*,
+    contracts::reserve_system,
+    economy::{Market, Money, SimClock, SimSet, Stock, shops_per_land},
+    game::{BuildKind, Building, ResourceKind, Shutdown},
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    save::SaveFile,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::{UITextButton, create_text_btn},
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+    },
+};
+
+const SAVE_SECTION: &str = "rules";
+
+pub fn rules_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_rules_system)
+        .add_systems(OnExit(screen), store_rules_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Automate.on_tick(rules_system.after(reserve_system)),
+        )
+        .add_screen_systems(screen, OnUpdate, update_rules_panel_system);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleAction {
+    Sell,
+    Buy,
+}
+
+impl RuleAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleAction::Sell => "Sell",
+            RuleAction::Buy => "Buy",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rule {
+    pub action: RuleAction,
+    pub kind: ResourceKind,
+    /// Sell above or buy below this amount
+    pub threshold: f32,
+    /// Minimum price to sell or maximum to buy, zero means any price
+    pub price_limit: f32,
+    /// Tick of the last trade, negative if it was before the game was loaded
+    pub last_fired: Option<i64>,
+    /// Total amount sold or bought by the rule
+    pub moved: f32,
+}
+
+impl Rule {
+    fn new(action: RuleAction, kind: ResourceKind, threshold: f32, price_limit: f32) -> Self {
+        Self {
+            action,
+            kind,
+            threshold,
+            price_limit,
+            last_fired: None,
+            moved: 0.0,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let (cond, cmp) = match self.action {
+            RuleAction::Sell => ("above", ">"),
+            RuleAction::Buy => ("below", "<"),
+        };
+        let mut txt = format!(
+            "{} {} when {cond} {:.0}",
+            self.action.name(),
+            self.kind.name(),
+            self.threshold
+        );
+        if self.price_limit > 0.0 {
+            txt.push_str(&format!(" and price {cmp} ${:.1}", self.price_limit));
+        }
+        txt
+    }
+
+    fn price_ok(&self, price: f32) -> bool {
+        if self.price_limit <= 0.0 {
+            return true;
+        }
+
+        match self.action {
+            RuleAction::Sell => price >= self.price_limit,
+            RuleAction::Buy => price <= self.price_limit,
+        }
+    }
+
+    /// Units that the rule wants to move this tick
+    pub fn amount(&self, stored: f32, capacity: f32, price: f32, max: f32) -> f32 {
+        if !self.price_ok(price) {
+            return 0.0;
+        }
+
+        let amount = match self.action {
+            RuleAction::Sell => stored - self.threshold,
+            RuleAction::Buy => (self.threshold - stored).min(capacity - stored),
+        };
+        amount.min(max).floor().max(0.0)
+    }
+
+    /// The last trade is kept as the ticks before the current one
+    fn encode(&self, tick: u64) -> String {
+        let ago = self
+            .last_fired
+            .map_or("-".to_string(), |t| (tick as i64 - t).max(0).to_string());
+        format!(
+            "{}|{}|{}|{}|{}|{}",
+            self.action.name(),
+            self.kind.name(),
+            self.threshold,
+            self.price_limit,
+            ago,
+            self.moved
+        )
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.split('|');
+        let action = match parts.next()? {
+            "Sell" => RuleAction::Sell,
+            "Buy" => RuleAction::Buy,
+            _ => return None,
+        };
+        let kind = ResourceKind::from_name(parts.next()?)?;
+        let threshold = parts.next()?.parse().ok()?;
+        let price_limit = parts.next()?.parse().ok()?;
+        let mut rule = Self::new(action, kind, threshold, price_limit);
+        rule.last_fired = match parts.next()? {
+            "-" => None,
+            ago => Some(-ago.parse::<i64>().ok()?),
+        };
+        rule.moved = parts.next()?.parse().ok()?;
+        Some(rule)
+    }
+}
+
+/// Rules of the player, saved with the profile so every game starts with them
+#[derive(Resource, Default)]
+pub struct Rules {
+    pub list: Vec<Rule>,
+}
+
+impl Rules {
+    fn load() -> Self {
+        let file = SaveFile::load();
+        let count: usize = file.get(SAVE_SECTION, "count").unwrap_or_default();
+        let list = (0..count.min(MAX_RULES))
+            .filter_map(|i| {
+                let value: String = file.get(SAVE_SECTION, &format!("rule{i}"))?;
+                Rule::decode(&value)
+            })
+            .collect();
+        Self { list }
+    }
+
+    fn store(&self, tick: u64) {
+        let mut values = vec![("count".to_string(), self.list.len().to_string())];
+        values.extend(
+            self.list
+                .iter()
+                .enumerate()
+                .map(|(i, rule)| (format!("rule{i}"), rule.encode(tick))),
+        );
+        let values = values
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.clone()))
+            .collect::<Vec<_>>();
+        SaveFile::update_section(SAVE_SECTION, &values);
+    }
+
+    /// The resource is traded by a rule instead of by the shops, buying and
+    /// selling it back would only move the price
+    pub fn trades(&self, kind: ResourceKind) -> bool {
+        self.list.iter().any(|r| r.kind == kind)
+    }
+}
+
+// - systems
+fn init_rules_system(mut cmds: Commands) {
+    cmds.insert_resource(Rules::load());
+    cmds.insert_resource(RuleDraft(Rule::new(
+        RuleAction::Sell,
+        ResourceKind::Copper,
+        100.0,
+        0.0,
+    )));
+}
+
+/// Rules trade through the shops, at the same rate, using the stock
+/// of the land where each shop is
+fn rules_system(
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+    mut stocks: Query<&mut Stock>,
+    mut rules: ResMut<Rules>,
+    mut market: ResMut<Market>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+    mut shake: ResMut<CamShake>,
+    clock: Res<SimClock>,
+) {
+    if rules.list.is_empty() {
+        return;
+    }
+
+    // stats change every tick, the rows only need to be rebuilt when the list changes
+    let rules = rules.bypass_change_detection();
+    shops_per_land(buildings.iter())
+        .into_iter()
+        .for_each(|(land, shops)| {
+            let Ok(mut stock) = stocks.get_mut(land) else {
+                return;
+            };
+
+            let max_amount = shops as f32 * SHOP_SELL_RATE;
+            rules.list.iter_mut().for_each(|rule| {
+                let kind = rule.kind;
+                let price = market.price(kind);
+                // the units reserved for the contracts are not for sale
+                let stored = match rule.action {
+                    RuleAction::Sell => stock.free(kind),
+                    RuleAction::Buy => stock.get(kind),
+                };
+                let mut amount = rule.amount(stored, stock.capacity(kind), price, max_amount);
+                match rule.action {
+                    RuleAction::Sell => {
+                        if amount <= 0.0 || !stock.take(kind, amount) {
+                            return;
+                        }
+
+                        let earned = market.sell(kind, amount);
+                        money.0 += earned;
+                        ledger.money(LedgerCategory::Sales, earned);
+                        ledger.resource(LedgerCategory::Sales, kind, -amount);
+                        shake.trade(earned);
+                    }
+                    RuleAction::Buy => {
+                        // the units get dearer as they are bought, the last is the top price
+                        let top = market.price_after_buy(kind, amount);
+                        if top > 0.0 {
+                            amount = amount.min((money.0 / top).floor());
+                        }
+
+                        if amount <= 0.0 {
+                            return;
+                        }
+
+                        let spent = market.buy(kind, amount);
+                        money.0 -= spent;
+                        stock.add(kind, amount);
+                        ledger.money(LedgerCategory::Purchases, -spent);
+                        ledger.resource(LedgerCategory::Purchases, kind, amount);
+                        shake.trade(spent);
+                    }
+                }
+
+                rule.last_fired = Some(clock.tick as i64);
+                rule.moved += amount;
+            });
+        });
+
+    // the stats are saved once a day
+    if clock.tick % DAY_TICKS as u64 == 0 {
+        rules.store(clock.tick);
+    }
+}
+
+fn store_rules_system(rules: Res<Rules>, clock: Res<SimClock>) {
+    rules.store(clock.tick);
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+struct RulesPanel;
+
+#[derive(Component, Clone, Copy)]
+struct RulesList;
+
+#[derive(Component, Clone, Copy)]
+struct RuleRow;
+
+#[derive(Component, Clone, Copy)]
+struct RuleText(usize);
+
+#[derive(Component, Clone, Copy)]
+struct RemoveRuleBtn(usize);
+
+#[derive(Component, Clone, Copy)]
+struct DraftText;
+
+/// Rule being edited before adding it to the list
+#[derive(Resource, Clone, Copy)]
+struct RuleDraft(Rule);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+enum DraftBtn {
+    Action,
+    Kind,
+    ThresholdDown,
+    ThresholdUp,
+    PriceDown,
+    PriceUp,
+    Add,
+}
+
+impl DraftBtn {
+    fn text(&self) -> &'static str {
+        match self {
+            DraftBtn::Action => "Action",
+            DraftBtn::Kind => "Res",
+            DraftBtn::ThresholdDown => "-Qty",
+            DraftBtn::ThresholdUp => "+Qty",
+            DraftBtn::PriceDown => "-$",
+            DraftBtn::PriceUp => "+$",
+            DraftBtn::Add => "Add",
+        }
+    }
+}
+
+pub fn create_rules_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Rules",
+        48.0,
+        UIOnClick::run(toggle_rules_panel_system),
+    )
+}
+
+fn toggle_rules_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<RulesPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Rules", RulesPanel);
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            panel_text("Rules trade at each land with a shop", PICO8_LIGHT_GRAY),
+        );
+
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (
+                RulesList,
+                UIContainer::default(),
+                UIStyle::default().flex_col().gap_y(1.0),
+            ),
+        );
+
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (DraftText, panel_text("", PICO8_WHITE)),
+        );
+
+        let row = spawn_panel_node(cmds, panel, content, panel_row());
+        [
+            DraftBtn::Action,
+            DraftBtn::Kind,
+            DraftBtn::ThresholdDown,
+            DraftBtn::ThresholdUp,
+            DraftBtn::PriceDown,
+            DraftBtn::PriceUp,
+            DraftBtn::Add,
+        ]
+        .into_iter()
+        .for_each(|field| {
+            let btn = create_text_btn(
+                cmds,
+                UIGameLayout,
+                field.text(),
+                24.0,
+                (PanelNode(panel), field, UIOnClick::run(edit_draft_system)),
+            );
+            cmds.add_ui_child(UIGameLayout, row, btn);
+        });
+    });
+}
+
+fn edit_draft_system(
+    In(entity): In<Entity>,
+    btns: Query<&DraftBtn>,
+    mut draft: ResMut<RuleDraft>,
+    mut rules: ResMut<Rules>,
+    mut notifications: ResMut<Notifications>,
+    clock: Res<SimClock>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    let rule = &mut draft.0;
+    match btn {
+        DraftBtn::Action => {
+            rule.action = match rule.action {
+                RuleAction::Sell => RuleAction::Buy,
+                RuleAction::Buy => RuleAction::Sell,
+            };
+        }
+        DraftBtn::Kind => {
+            let kinds = ResourceKind::iter()
+                .filter(|k| k.is_buyable())
+                .collect::<Vec<_>>();
+            let idx = kinds.iter().position(|k| *k == rule.kind).unwrap_or(0);
+            rule.kind = kinds[(idx + 1) % kinds.len()];
+        }
+        DraftBtn::ThresholdDown => {
+            rule.threshold = (rule.threshold - RULE_THRESHOLD_STEP).max(0.0);
+        }
+        DraftBtn::ThresholdUp => rule.threshold += RULE_THRESHOLD_STEP,
+        DraftBtn::PriceDown => {
+            rule.price_limit = (rule.price_limit - RULE_PRICE_STEP).max(0.0);
+        }
+        DraftBtn::PriceUp => rule.price_limit += RULE_PRICE_STEP,
+        DraftBtn::Add => {
+            if rule.action == RuleAction::Sell && !rule.kind.is_tradable() {
+                notifications.push(
+                    format!("{} can't be sold", rule.kind.name()),
+                    PICO8_ORANGE,
+                    None,
+                );
+                return;
+            }
+
+            if rules.list.len() >= MAX_RULES {
+                notifications.push("Too many rules", PICO8_ORANGE, None);
+                return;
+            }
+
+            rules.list.push(*rule);
+            rules.store(clock.tick);
+        }
+    }
+}
+
+fn remove_rule_system(
+    In(entity): In<Entity>,
+    btns: Query<&RemoveRuleBtn>,
+    mut rules: ResMut<Rules>,
+    clock: Res<SimClock>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    if btn.0 < rules.list.len() {
+        rules.list.remove(btn.0);
+        rules.store(clock.tick);
+    }
+}
+
+fn update_rules_panel_system(
+    mut cmds: Commands,
+    mut draft_text: Query<&mut UIText, With<DraftText>>,
+    mut rule_texts: Query<(&mut UIText, &RuleText), Without<DraftText>>,
+    mut add_btn: Query<(&mut UITextButton, &DraftBtn)>,
+    list: Query<(Entity, &PanelNode), With<RulesList>>,
+    new_list: Query<(), Added<RulesList>>,
+    rows: Query<Entity, With<RuleRow>>,
+    rules: Res<Rules>,
+    draft: Res<RuleDraft>,
+    clock: Res<SimClock>,
+) {
+    let Ok((list, panel)) = list.get_single() else {
+        return;
+    };
+
+    draft_text.iter_mut().for_each(|mut text| {
+        text.text = format!("New: {}", draft.0.describe());
+    });
+
+    add_btn
+        .iter_mut()
+        .filter(|(_, field)| **field == DraftBtn::Add)
+        .for_each(|(mut btn, _)| btn.enabled = rules.list.len() < MAX_RULES);
+
+    rule_texts.iter_mut().for_each(|(mut text, idx)| {
+        let Some(rule) = rules.list.get(idx.0) else {
+            return;
+        };
+
+        let fired = match rule.last_fired {
+            Some(tick) => {
+                let secs = (clock.tick as i64 - tick).max(0) as f32 * SIM_TICK_TIME;
+                format!("{secs:.0}s ago")
+            }
+            None => "never".to_string(),
+        };
+        text.text = format!("{}: {fired}, moved {:.0}", rule.describe(), rule.moved);
+    });
+
+    // rows are rebuilt only when a rule is added or removed
+    if !rules.is_changed() && new_list.is_empty() {
+        return;
+    }
+
+    let layout = UIGameLayout;
+    rows.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+    rules.list.iter().enumerate().for_each(|(idx, _)| {
+        let row = spawn_panel_node(&mut cmds, panel.0, list, (RuleRow, panel_row()));
+        spawn_panel_node(
+            &mut cmds,
+            panel.0,
+            row,
+            (RuleRow, RuleText(idx), panel_text("", PICO8_PEACH)),
+        );
+        let btn = create_text_btn(
+            &mut cmds,
+            layout,
+            "X",
+            12.0,
+            (
+                PanelNode(panel.0),
+                RuleRow,
+                RemoveRuleBtn(idx),
+                UIOnClick::run(remove_rule_system),
+            ),
+        );
+        cmds.add_ui_child(layout, row, btn);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_rule_keeps_its_stats() {
+        let mut rule = Rule::new(RuleAction::Buy, ResourceKind::Food, 20.0, 0.5);
+        rule.last_fired = Some(90);
+        rule.moved = 135.0;
+
+        // saved at tick 100, the next game starts at tick 0
+        let loaded = Rule::decode(&rule.encode(100)).unwrap();
+        assert_eq!(loaded.action, RuleAction::Buy);
+        assert_eq!(loaded.kind, ResourceKind::Food);
+        assert_eq!(loaded.threshold, 20.0);
+        assert_eq!(loaded.price_limit, 0.5);
+        assert_eq!(loaded.last_fired, Some(-10));
+        assert_eq!(loaded.moved, 135.0);
+    }
+
+    #[test]
+    fn rule_that_never_fired_stays_that_way() {
+        let rule = Rule::new(RuleAction::Sell, ResourceKind::Copper, 200.0, 0.0);
+        let loaded = Rule::decode(&rule.encode(50)).unwrap();
+        assert_eq!(loaded.last_fired, None);
+        assert_eq!(loaded.moved, 0.0);
+    }
+
+    #[test]
+    fn buying_and_selling_back_loses_money() {
+        let mut market = Market::default();
+        let price = market.price(ResourceKind::Gold);
+        let spent = market.buy(ResourceKind::Gold, 10.0);
+        assert!(spent > price * 10.0);
+
+        let earned = market.sell(ResourceKind::Gold, 10.0);
+        assert!(earned < spent, "earned {earned} of {spent}");
+    }
+
+    #[test]
+    fn shops_leave_the_ruled_kinds_alone() {
+        let rules = Rules {
+            list: vec![Rule::new(RuleAction::Buy, ResourceKind::Gold, 20.0, 10.0)],
+        };
+        assert!(rules.trades(ResourceKind::Gold));
+        assert!(!rules.trades(ResourceKind::Copper));
+    }
+}
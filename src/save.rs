@@ -0,0 +1,84 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crate::platform::{load_storage, save_storage};
+
+const SAVE_NAME: &str = "ld57";
+
+/// Save file split in sections, each one owned by a different system
+/// so they can be stored and reset independently.
+///
+/// ```text
+/// [prestige]
+/// depth=2
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SaveFile {
+    sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl SaveFile {
+    pub fn load() -> Self {
+        load_storage(SAVE_NAME)
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self) {
+        if let Err(e) = save_storage(SAVE_NAME, &self.to_string()) {
+            log::error!("Unable to save the game: {e}");
+        }
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut file = Self::default();
+        let mut section = String::new();
+        text.lines().map(str::trim).for_each(|line| {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.to_string();
+            } else if let Some((key, value)) = line.split_once('=') {
+                file.sections
+                    .entry(section.clone())
+                    .or_default()
+                    .insert(key.to_string(), value.to_string());
+            }
+        });
+        file
+    }
+
+    pub fn section_names(&self) -> impl Iterator<Item = &str> {
+        self.sections.keys().map(String::as_str)
+    }
+
+    pub fn get<T: FromStr>(&self, section: &str, key: &str) -> Option<T> {
+        self.sections.get(section)?.get(key)?.parse().ok()
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: impl Display) {
+        self.sections
+            .entry(section.to_string())
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    /// Loads the file, replaces the section and stores it again
+    pub fn update_section(section: &str, values: &[(&str, String)]) {
+        let mut file = Self::load();
+        file.sections.remove(section);
+        values
+            .iter()
+            .for_each(|(key, value)| file.set(section, key, value));
+        file.store();
+    }
+}
+
+impl Display for SaveFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, values) in &self.sections {
+            writeln!(f, "[{name}]")?;
+            for (key, value) in values {
+                writeln!(f, "{key}={value}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,32 +4,59 @@ use rkit::prelude::*;
 pub enum AppScreen {
     Load,
     Game,
+    GameOver,
+    Deeper,
 }
 
 pub fn screens_plugin(app: &mut App) {
     app.with_screen(AppScreen::Load)
         .add_plugin(load_screen::plugin)
-        .add_plugin(game_screen::plugin);
+        .add_plugin(game_screen::plugin)
+        .add_plugin(game_over_screen::plugin)
+        .add_plugin(deeper_screen::plugin);
 }
 
 mod game_screen {
     use std::ops::Deref;
 
-    use rkit::{gfx::Color, math::vec2, prelude::*};
+    use rkit::{draw::HAlign, gfx::Color, math::vec2, prelude::*};
+    use strum::IntoEnumIterator;
 
     use crate::{
         assets::Assets,
+        bank::create_bank_btn,
+        build::{Tool, on_tool_click_system},
+        calendar::CalendarText,
         camera::{Cam, GameCam, UICam},
         consts::{
-            PICO8_BLACK, PICO8_BLUE, PICO8_BROWN, PICO8_DARK_PURPLE, PICO8_INDIGO, PICO8_ORANGE,
-            PICO8_PEACH, PICO8_RED, PICO8_WHITE,
+            PICO8_BLACK, PICO8_BLUE, PICO8_BROWN, PICO8_DARK_PURPLE, PICO8_INDIGO,
+            PICO8_LIGHT_GRAY, PICO8_ORANGE, PICO8_PEACH, PICO8_RED, PICO8_WHITE,
+            STORAGE_WARNING_RATIO,
         },
-        game::{ResourceKind, game_plugin},
+        contracts::create_desk_btn,
+        economy::{Money, TotalStock},
+        game::{BuildKind, ResourceKind, game_plugin},
+        hazards::Safety,
+        inflation::InflationText,
+        ledger::ledger_info,
+        logistics::create_routes_btn,
+        minimap::create_minimap,
+        notify::NotificationList,
+        pollution::create_pollution_btn,
+        prestige::create_prestige_btn,
+        rivals::create_leaderboard_btn,
+        rules::create_rules_btn,
+        seed::GameSeed,
+        settings::{create_motion_btn, create_resolution_btn, create_scaling_btn},
+        society::{TaxText, happiness_meter, tax_slider},
+        stats::{create_stats_btn, resource_info},
         ui::{
             UIGameLayout,
             btns::UIImgButton,
-            counter::{CounterInfo, create_img_counter},
+            click::UIOnClick,
+            counter::create_img_counter,
             load_bar::UILoadBar,
+            panel::PanelContainer,
             tooltip::{ResInfo, TooltipContainer, create_btn_info_tooltip},
         },
     };
@@ -38,7 +65,7 @@ mod game_screen {
     pub fn plugin(app: &mut App) {
         let screen = AppScreen::Game;
         app.add_plugin(UILayoutPlugin::<UIGameLayout>::default())
-            .add_screen_systems(screen, OnUpdate, update_system)
+            .add_screen_systems(screen, OnUpdate, (update_system, update_counters_system))
             .add_systems(OnEnter(screen), setup_system)
             .add_systems(OnExit(screen), cleanup_system)
             .add_plugin(game_plugin);
@@ -47,23 +74,9 @@ mod game_screen {
     #[derive(Debug, Component, Clone, Copy)]
     struct MoneyCounter;
     #[derive(Debug, Component, Clone, Copy)]
-    struct CopperCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct IronCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct SilverCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct GoldCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct WoodCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct FoodCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct PeopleCounter;
-    #[derive(Debug, Component, Clone, Copy)]
-    struct RingCounter;
+    struct ResourceCounter(ResourceKind);
 
-    fn setup_system(mut cmds: Commands, assets: Res<Assets>) {
+    fn setup_system(mut cmds: Commands, assets: Res<Assets>, seed: Res<GameSeed>) {
         let layout = UIGameLayout;
         let root = cmds
             .spawn_ui_node(
@@ -120,95 +133,23 @@ mod game_screen {
 
         cmds.add_ui_child(layout, top, counters_container);
 
-        {
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.copper,
-                CopperCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.iron,
-                IronCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.silver,
-                SilverCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.gold,
-                GoldCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.wood,
-                WoodCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.food,
-                FoodCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-
+        ResourceKind::iter().for_each(|kind| {
             let counter = create_img_counter(
                 &mut cmds,
                 layout,
-                &assets.people,
-                PeopleCounter,
+                assets.resource(kind),
+                ResourceCounter(kind),
                 PICO8_BLACK,
-                (),
+                resource_info(kind),
             );
 
             cmds.add_ui_child(layout, counters_container, counter);
 
-            let counter = create_img_counter(
-                &mut cmds,
-                layout,
-                &assets.ring,
-                RingCounter,
-                PICO8_BLACK,
-                (),
-            );
-
-            cmds.add_ui_child(layout, counters_container, counter);
-        }
+            if matches!(kind, ResourceKind::People) {
+                let meter = cmds.spawn_ui_node(layout, happiness_meter()).entity_id();
+                cmds.add_ui_child(layout, counter, meter);
+            }
+        });
 
         let money_container = cmds
             .spawn_ui_node(
@@ -219,9 +160,12 @@ mod game_screen {
                     },
                     UIStyle::default()
                         .width(Unit::Relative(0.2))
+                        .flex_col()
+                        .gap_y(2.0)
                         .padding_top(4.0)
                         .justify_content_center()
-                        .align_self_start(), // .align_items_center(),
+                        .align_self_start()
+                        .align_items_center(),
                 ),
             )
             .entity_id();
@@ -233,25 +177,136 @@ mod game_screen {
             &assets.money,
             MoneyCounter,
             PICO8_DARK_PURPLE,
-            (CounterInfo, UIPointer::default()),
+            ledger_info(),
         );
 
         cmds.add_ui_child(layout, money_container, counter);
 
+        let calendar_txt = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    CalendarText,
+                    UIText {
+                        text: String::new(),
+                        color: PICO8_WHITE,
+                        size: 8.0,
+                        h_align: HAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, money_container, calendar_txt);
+
+        let inflation_txt = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    InflationText,
+                    UIText {
+                        text: String::new(),
+                        color: PICO8_LIGHT_GRAY,
+                        size: 8.0,
+                        h_align: HAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, money_container, inflation_txt);
+
+        // share the seed so other players can generate the same world
+        let seed_txt = cmds
+            .spawn_ui_node(
+                layout,
+                UIText {
+                    text: format!("Seed: {}", seed.label()),
+                    color: PICO8_INDIGO,
+                    size: 8.0,
+                    h_align: HAlign::Center,
+                    ..Default::default()
+                },
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, money_container, seed_txt);
+
         let options_container = cmds
             .spawn_ui_node(
                 layout,
                 (
                     UIContainer {
-                        bg_color: Some(PICO8_RED),
                         ..Default::default()
                     },
-                    UIStyle::default().width(Unit::Relative(0.2)),
+                    UIStyle::default()
+                        .width(Unit::Relative(0.2))
+                        .flex_col()
+                        .gap_y(2.0)
+                        .padding_top(4.0)
+                        .padding_right(4.0)
+                        .align_self_start()
+                        .align_items_end(),
                 ),
             )
             .entity_id();
         cmds.add_ui_child(layout, top, options_container);
 
+        let bank_btn = create_bank_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, bank_btn);
+
+        let pollution_btn = create_pollution_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, pollution_btn);
+
+        let stats_btn = create_stats_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, stats_btn);
+
+        let prestige_btn = create_prestige_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, prestige_btn);
+
+        let rules_btn = create_rules_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, rules_btn);
+
+        let routes_btn = create_routes_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, routes_btn);
+
+        let rivals_btn = create_leaderboard_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, rivals_btn);
+
+        let desk_btn = create_desk_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, desk_btn);
+
+        let motion_btn = create_motion_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, motion_btn);
+
+        let resolution_btn = create_resolution_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, resolution_btn);
+
+        let scaling_btn = create_scaling_btn(&mut cmds);
+        cmds.add_ui_child(layout, options_container, scaling_btn);
+
+        let tax_txt = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    TaxText,
+                    UIText {
+                        text: String::new(),
+                        color: PICO8_WHITE,
+                        size: 8.0,
+                        h_align: HAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+            )
+            .entity_id();
+        cmds.add_ui_child(layout, options_container, tax_txt);
+
+        let tax_slider = cmds.spawn_ui_node(layout, tax_slider()).entity_id();
+        cmds.add_ui_child(layout, options_container, tax_slider);
+
         let bottom = cmds
             .spawn_ui_node(
                 layout,
@@ -262,7 +317,7 @@ mod game_screen {
                     },
                     UIStyle::default()
                         .flex_row()
-                        .gap_x(8.0)
+                        .gap_x(2.0)
                         .size_full()
                         .justify_content_center()
                         .align_items_end()
@@ -272,119 +327,123 @@ mod game_screen {
             .entity_id();
         cmds.add_ui_child(layout, root, bottom);
 
-        {
-            let btn1 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.land.clone(),
-                            text: "Land".to_string(),
-                            enabled: false,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
-
-            cmds.add_ui_child(layout, bottom, btn1);
-
-            let btn2 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.mine.clone(),
-                            text: "Mine".to_string(),
-                            enabled: false,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
-
-            cmds.add_ui_child(layout, bottom, btn2);
+        let tools = [
+            (Tool::BuyLand, &assets.land, "Land"),
+            (Tool::Build(BuildKind::Mine), &assets.mine, "Mine"),
+            (Tool::Build(BuildKind::Shop), &assets.shop, "Shop"),
+            (Tool::Build(BuildKind::Factory), &assets.factory, "Factory"),
+            (Tool::Build(BuildKind::Forest), &assets.forest, "Forest"),
+            (Tool::Build(BuildKind::House), &assets.house, "House"),
+            (Tool::Build(BuildKind::Farm), &assets.farm, "Farm"),
+            (
+                Tool::Build(BuildKind::Warehouse),
+                assets.building(BuildKind::Warehouse),
+                "Warehouse",
+            ),
+            (
+                Tool::Build(BuildKind::Depot),
+                assets.building(BuildKind::Depot),
+                "Depot",
+            ),
+            (Tool::Dig, &assets.dotted_square, "Dig"),
+            (Tool::Abandon, &assets.empty_square, "Abandon"),
+            (Tool::Secure(Safety::Pump), &assets.white_square, "Pump"),
+            (
+                Tool::Secure(Safety::Ventilation),
+                &assets.dotted_square,
+                "Vent",
+            ),
+            (
+                Tool::Secure(Safety::Supports),
+                &assets.empty_square,
+                "Supports",
+            ),
+            (Tool::Demolish, &assets.empty_square, "Demolish"),
+            (Tool::Move, &assets.dotted_square, "Move"),
+        ];
 
-            let btn3 = cmds
+        tools.into_iter().for_each(|(tool, sprite, text)| {
+            let btn = cmds
                 .spawn_ui_node(
                     layout,
                     (
+                        tool,
                         UIImgButton {
-                            sprite: assets.shop.clone(),
-                            text: "Shop".to_string(),
+                            sprite: sprite.clone(),
+                            text: text.to_string(),
                             enabled: false,
                         },
-                        UIStyle::default().size(32.0, 32.0),
+                        UIPointer::default(),
+                        UIOnClick::run(on_tool_click_system),
+                        UIStyle::default().size(28.0, 28.0),
                     ),
                 )
                 .entity_id();
 
-            cmds.add_ui_child(layout, bottom, btn3);
-
-            let btn4 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.factory.clone(),
-                            text: "Factory".to_string(),
-                            enabled: false,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
+            cmds.add_ui_child(layout, bottom, btn);
+        });
 
-            cmds.add_ui_child(layout, bottom, btn4);
+        let minimap_container = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    UIContainer::default(),
+                    UIStyle::default()
+                        .absolute()
+                        .size_full()
+                        .flex_col()
+                        .justify_content_end()
+                        .align_items_start()
+                        .padding_left(4.0)
+                        // above the toolbar and its labels
+                        .padding_bottom(52.0),
+                ),
+            )
+            .entity_id();
 
-            let btn5 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.forest.clone(),
-                            text: "Forest".to_string(),
-                            enabled: true,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
+        cmds.add_ui_child(layout, root, minimap_container);
 
-            cmds.add_ui_child(layout, bottom, btn5);
+        let minimap = create_minimap(&mut cmds);
+        cmds.add_ui_child(layout, minimap_container, minimap);
 
-            let btn6 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.house.clone(),
-                            text: "House".to_string(),
-                            enabled: false,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
+        let notifications = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    NotificationList,
+                    UIContainer::default(),
+                    UIStyle::default()
+                        .absolute()
+                        .size_full()
+                        .flex_col()
+                        .gap_y(2.0)
+                        .padding_top(24.0)
+                        .padding_right(4.0)
+                        .align_items_end(),
+                ),
+            )
+            .entity_id();
 
-            cmds.add_ui_child(layout, bottom, btn6);
+        cmds.add_ui_child(layout, root, notifications);
 
-            let btn7 = cmds
-                .spawn_ui_node(
-                    layout,
-                    (
-                        UIImgButton {
-                            sprite: assets.farm.clone(),
-                            text: "Farm".to_string(),
-                            enabled: false,
-                        },
-                        UIStyle::default().size(32.0, 32.0),
-                    ),
-                )
-                .entity_id();
+        let panels = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    PanelContainer,
+                    UIContainer::default(),
+                    UIStyle::default()
+                        .absolute()
+                        .size_full()
+                        .flex_row()
+                        .gap_x(4.0)
+                        .justify_content_center()
+                        .align_items_center(),
+                ),
+            )
+            .entity_id();
 
-            cmds.add_ui_child(layout, bottom, btn7);
-        }
+        cmds.add_ui_child(layout, root, panels);
 
         let tooltip = cmds
             .spawn_ui_node(
@@ -433,6 +492,34 @@ mod game_screen {
         let ui_cam = cam.into_inner();
         layout.set_camera(&ui_cam.cam);
     }
+
+    fn update_counters_system(
+        mut resources: Query<(&mut UIText, &ResourceCounter), Without<MoneyCounter>>,
+        money_text: Single<&mut UIText, With<MoneyCounter>>,
+        stock: Res<TotalStock>,
+        money: Res<Money>,
+    ) {
+        resources.iter_mut().for_each(|(mut text, counter)| {
+            let amount = stock.get(counter.0).floor();
+            let capacity = stock.capacity(counter.0);
+            text.text = format!("{amount:.0}/{capacity:.0}");
+            text.color = if amount >= capacity {
+                PICO8_RED
+            } else if amount >= capacity * STORAGE_WARNING_RATIO {
+                PICO8_ORANGE
+            } else {
+                PICO8_WHITE
+            };
+        });
+
+        let mut money_text = money_text.into_inner();
+        money_text.text = format!("{:.0}", money.0.floor());
+        money_text.color = if money.0 < 0.0 {
+            PICO8_RED
+        } else {
+            PICO8_WHITE
+        };
+    }
 }
 
 mod load_screen {
@@ -445,6 +532,7 @@ mod load_screen {
         camera::{Cam, UICam},
         consts::*,
         postfx::rtf,
+        seed::GameSeed,
         ui::{UILoadLayout, load_bar::UILoadBar},
     };
 
@@ -524,6 +612,12 @@ mod load_screen {
         }
 
         *style = style.hide();
+        if keyboard.just_pressed(KeyCode::KeyD) {
+            cmds.insert_resource(GameSeed::daily());
+            cmds.queue(ChangeScreen(AppScreen::Game));
+            return;
+        }
+
         let mouse_interaction = !mouse.pressed_buttons().is_empty();
         let keyboard_interaction = !keyboard.pressed_keys().is_empty();
         let did_interact = mouse_interaction || keyboard_interaction;
@@ -550,6 +644,8 @@ mod load_screen {
         draw.set_round_pixels(true);
         draw.set_camera(cam.deref());
         draw.clear(PICO8_DARK_GRAY);
+        let center = cam.resolution * 0.5;
+        let max_width = cam.resolution.x * 0.8;
         draw_ui_layout::<UILoadLayout>(&mut draw, world);
 
         {
@@ -562,8 +658,8 @@ mod load_screen {
                 let alpha = 0.5 + 0.5 * (t * 2.0).sin();
                 draw.text(TITLE)
                     .origin(vec2(0.5, 1.0))
-                    .translate(RESOLUTION * 0.5)
-                    .max_width(RESOLUTION.x * 0.8)
+                    .translate(center)
+                    .max_width(max_width)
                     .h_align_center()
                     .color(PICO8_BLACK)
                     .size(24.0);
@@ -571,14 +667,139 @@ mod load_screen {
                 draw.text("Click or press to start")
                     .alpha(alpha)
                     .origin(vec2(0.5, 0.0))
-                    .translate(RESOLUTION * 0.5 + vec2(0.0, 24.0))
-                    .max_width(RESOLUTION.x * 0.8)
+                    .translate(center + vec2(0.0, 24.0))
+                    .max_width(max_width)
                     .h_align_center()
                     .color(PICO8_INDIGO)
                     .size(12.0);
+
+                draw.text("Press D to play the daily seed")
+                    .origin(vec2(0.5, 0.0))
+                    .translate(center + vec2(0.0, 44.0))
+                    .max_width(max_width)
+                    .h_align_center()
+                    .color(PICO8_DARK_BLUE)
+                    .size(8.0);
             }
         }
 
         rtf(&draw).unwrap();
     }
 }
+
+mod game_over_screen {
+    use std::ops::Deref;
+
+    use rkit::{draw::create_draw_2d, math::vec2, prelude::*};
+
+    use crate::{
+        camera::{Cam, UICam},
+        consts::*,
+        postfx::rtf,
+    };
+
+    use super::AppScreen;
+
+    pub fn plugin(app: &mut App) {
+        let screen = AppScreen::GameOver;
+        app.add_screen_systems(screen, OnUpdate, restart_system)
+            .add_screen_systems(screen, OnRender, draw_system);
+    }
+
+    fn restart_system(mut cmds: Commands, mouse: Res<Mouse>) {
+        if mouse.just_pressed(MouseButton::Left) {
+            cmds.queue(ChangeScreen(AppScreen::Game));
+        }
+    }
+
+    fn draw_system(world: &mut World) {
+        let cam = world.query_filtered::<&Cam, With<UICam>>().single(world);
+        let mut draw = create_draw_2d();
+        draw.set_round_pixels(true);
+        draw.set_camera(cam.deref());
+        draw.clear(PICO8_BLACK);
+
+        let center = cam.resolution * 0.5;
+        draw.text("Bankrupt")
+            .origin(vec2(0.5, 1.0))
+            .translate(center)
+            .h_align_center()
+            .color(PICO8_RED)
+            .size(24.0);
+
+        let t = world.resource::<Time>().elapsed_f32();
+        let alpha = 0.5 + 0.5 * (t * 2.0).sin();
+        draw.text("Click to try again")
+            .alpha(alpha)
+            .origin(vec2(0.5, 0.0))
+            .translate(center + vec2(0.0, 24.0))
+            .h_align_center()
+            .color(PICO8_INDIGO)
+            .size(12.0);
+
+        rtf(&draw).unwrap();
+    }
+}
+
+mod deeper_screen {
+    use std::ops::Deref;
+
+    use rkit::{draw::create_draw_2d, math::vec2, prelude::*};
+
+    use crate::{
+        camera::{Cam, UICam},
+        consts::*,
+        postfx::rtf,
+        prestige::Prestige,
+    };
+
+    use super::AppScreen;
+
+    pub fn plugin(app: &mut App) {
+        let screen = AppScreen::Deeper;
+        app.add_screen_systems(screen, OnUpdate, continue_system)
+            .add_screen_systems(screen, OnRender, draw_system);
+    }
+
+    fn continue_system(mut cmds: Commands, mouse: Res<Mouse>) {
+        if mouse.just_pressed(MouseButton::Left) {
+            cmds.queue(ChangeScreen(AppScreen::Game));
+        }
+    }
+
+    fn draw_system(world: &mut World) {
+        let cam = world.query_filtered::<&Cam, With<UICam>>().single(world);
+        let mut draw = create_draw_2d();
+        draw.set_round_pixels(true);
+        draw.set_camera(cam.deref());
+        draw.clear(PICO8_BLACK);
+
+        let prestige = world.resource::<Prestige>();
+        let center = cam.resolution * 0.5;
+        draw.text(&format!("Depth {}", prestige.depth))
+            .origin(vec2(0.5, 1.0))
+            .translate(center)
+            .h_align_center()
+            .color(PICO8_PEACH)
+            .size(24.0);
+
+        draw.text(&format!("{} shards to spend", prestige.shards))
+            .origin(vec2(0.5, 0.0))
+            .translate(center + vec2(0.0, 8.0))
+            .h_align_center()
+            .color(PICO8_LIGHT_GRAY)
+            .size(8.0);
+
+        let t = world.resource::<Time>().elapsed_f32();
+        let alpha = 0.5 + 0.5 * (t * 2.0).sin();
+        draw.text("Click to go deeper")
+            .alpha(alpha)
+            .origin(vec2(0.5, 0.0))
+            .translate(center + vec2(0.0, 24.0))
+            .h_align_center()
+            .color(PICO8_INDIGO)
+            .size(12.0);
+
+        rtf(&draw).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use rkit::prelude::*;
+
+use crate::platform;
+
+// Salt used to derive the daily seed from the current day
+const DAILY_SALT: u64 = 0xDA11_5EED;
+
+#[derive(Resource, Clone, Copy, Debug, Deref)]
+pub struct GameSeed {
+    #[deref]
+    pub value: u64,
+    pub daily: bool,
+}
+
+impl GameSeed {
+    /// Seed passed as argument (`--seed` or `?seed=`) or a new random one
+    pub fn from_args_or_random() -> Self {
+        let value = platform::arg("seed")
+            .and_then(|s| parse_seed(&s))
+            .unwrap_or_else(|| SeedRng::fork(platform::now_secs(), 0).next_u64());
+        Self {
+            value,
+            daily: false,
+        }
+    }
+
+    /// Same seed for everyone during the same UTC day
+    pub fn daily() -> Self {
+        let day = platform::now_secs() / 86_400;
+        Self {
+            value: SeedRng::fork(day, DAILY_SALT).next_u64(),
+            daily: true,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let seed = format_seed(self.value);
+        if self.daily {
+            format!("Daily {seed}")
+        } else {
+            seed
+        }
+    }
+}
+
+// Seeds are shared as hexadecimal strings
+pub fn format_seed(seed: u64) -> String {
+    format!("{seed:X}")
+}
+
+pub fn parse_seed(txt: &str) -> Option<u64> {
+    u64::from_str_radix(txt.trim(), 16).ok()
+}
+
+/// SplitMix64 generator. Only integer ops are used to produce values
+/// so the same seed gives the same sequence on every platform.
+#[derive(Clone, Debug)]
+pub struct SeedRng {
+    state: u64,
+}
+
+impl SeedRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Derive an independent generator from a seed and a salt
+    pub fn fork(seed: u64, salt: u64) -> Self {
+        let mut rng = Self::new(seed ^ salt.wrapping_mul(0xA076_1D64_78BD_642F));
+        rng.next_u64();
+        rng
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Value in the range [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Value in the range [min, max)
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        debug_assert!(min < max);
+        min + (self.next_u64() % (max - min) as u64) as u32
+    }
+
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitmix64_reference_output() {
+        let mut rng = SeedRng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+        assert_eq!(rng.next_u64(), 0x06C4_5D18_8009_454F);
+
+        let mut rng = SeedRng::new(1_234_567);
+        assert_eq!(rng.next_u64(), 6_457_827_717_110_365_317);
+        assert_eq!(rng.next_u64(), 3_203_168_211_198_807_973);
+        assert_eq!(rng.next_u64(), 9_817_491_932_198_370_423);
+    }
+
+    #[test]
+    fn fork_is_pinned() {
+        let mut rng = SeedRng::fork(0x1D57_EC0D_DEE9, 7);
+        assert_eq!(rng.next_u64(), 0x7F66_73A9_AF4F_A171);
+        assert_eq!(rng.next_f32(), 0.551_383_44);
+        assert_eq!(rng.range(10, 20), 14);
+    }
+
+    #[test]
+    fn seed_text_round_trip() {
+        let seed = 0x1D57_EC0D_DEE9;
+        assert_eq!(parse_seed(&format_seed(seed)), Some(seed));
+        assert_eq!(parse_seed(" 1d57ec0ddee9 "), Some(seed));
+        assert_eq!(parse_seed("not a seed"), None);
+    }
+}
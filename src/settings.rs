@@ -0,0 +1,200 @@
+use rkit::{math::Vec2, prelude::*};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    consts::*,
+    save::SaveFile,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::{UITextButton, create_text_btn},
+        click::UIOnClick,
+    },
+};
+
+const SAVE_SECTION: &str = "settings";
+
+pub fn settings_plugin(app: &mut App) {
+    app.add_resource(Settings::load()).add_screen_systems(
+        AppScreen::Game,
+        OnUpdate,
+        update_settings_btns_system,
+    );
+}
+
+/// Internal resolution of the game, the ui keeps its proportion
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter)]
+pub enum ResolutionPreset {
+    #[default]
+    Small,
+    Medium,
+    Large,
+}
+
+impl ResolutionPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResolutionPreset::Small => "400x225",
+            ResolutionPreset::Medium => "640x360",
+            ResolutionPreset::Large => "800x450",
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            ResolutionPreset::Small => RESOLUTION,
+            ResolutionPreset::Medium => Vec2::new(640.0, 360.0),
+            ResolutionPreset::Large => Vec2::new(800.0, 450.0),
+        }
+    }
+
+    pub fn ui_size(&self) -> Vec2 {
+        match self {
+            ResolutionPreset::Small => UI_RESOLUTION,
+            ResolutionPreset::Medium => Vec2::new(768.0, 432.0),
+            ResolutionPreset::Large => Vec2::new(1024.0, 576.0),
+        }
+    }
+}
+
+/// How the internal resolution fills the window
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter)]
+pub enum Scaling {
+    /// Keeps the aspect ratio, showing more of the world if needed
+    #[default]
+    AspectFit,
+    /// Only whole multiples of the resolution, sharpest pixels
+    Integer,
+    /// Fills the whole window deforming the pixels
+    Stretch,
+}
+
+impl Scaling {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scaling::AspectFit => "Fit",
+            Scaling::Integer => "Integer",
+            Scaling::Stretch => "Stretch",
+        }
+    }
+}
+
+/// Next variant of the enum, going back to the first one after the last
+fn cycle<T: IntoEnumIterator + PartialEq + Default>(value: T) -> T {
+    let mut iter = T::iter().skip_while(|v| *v != value).skip(1);
+    iter.next().unwrap_or_default()
+}
+
+/// Player preferences kept between runs
+#[derive(Resource, Default, Debug, Clone)]
+pub struct Settings {
+    /// Disables the effects that move the whole screen
+    pub reduced_motion: bool,
+    pub resolution: ResolutionPreset,
+    pub scaling: Scaling,
+}
+
+impl Settings {
+    fn load() -> Self {
+        let file = SaveFile::load();
+        let name = |key| file.get::<String>(SAVE_SECTION, key).unwrap_or_default();
+        let resolution = name("resolution");
+        let scaling = name("scaling");
+        Self {
+            reduced_motion: file.get(SAVE_SECTION, "reduced_motion").unwrap_or_default(),
+            resolution: ResolutionPreset::iter()
+                .find(|p| p.name() == resolution)
+                .unwrap_or_default(),
+            scaling: Scaling::iter()
+                .find(|s| s.name() == scaling)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn store(&self) {
+        SaveFile::update_section(
+            SAVE_SECTION,
+            &[
+                ("reduced_motion", self.reduced_motion.to_string()),
+                ("resolution", self.resolution.name().to_string()),
+                ("scaling", self.scaling.name().to_string()),
+            ],
+        );
+    }
+}
+
+// - ui
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+enum SettingsBtn {
+    Motion,
+    Resolution,
+    Scaling,
+}
+
+impl SettingsBtn {
+    fn label(&self, settings: &Settings) -> &'static str {
+        match self {
+            SettingsBtn::Motion if settings.reduced_motion => "No shake",
+            SettingsBtn::Motion => "Shake",
+            SettingsBtn::Resolution => settings.resolution.name(),
+            SettingsBtn::Scaling => settings.scaling.name(),
+        }
+    }
+
+    fn apply(&self, settings: &mut Settings) {
+        match self {
+            SettingsBtn::Motion => settings.reduced_motion = !settings.reduced_motion,
+            SettingsBtn::Resolution => settings.resolution = cycle(settings.resolution),
+            SettingsBtn::Scaling => settings.scaling = cycle(settings.scaling),
+        }
+    }
+}
+
+fn create_settings_btn(cmds: &mut Commands, btn: SettingsBtn) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "",
+        48.0,
+        (btn, UIOnClick::run(on_settings_btn_click_system)),
+    )
+}
+
+pub fn create_motion_btn(cmds: &mut Commands) -> Entity {
+    create_settings_btn(cmds, SettingsBtn::Motion)
+}
+
+pub fn create_resolution_btn(cmds: &mut Commands) -> Entity {
+    create_settings_btn(cmds, SettingsBtn::Resolution)
+}
+
+pub fn create_scaling_btn(cmds: &mut Commands) -> Entity {
+    create_settings_btn(cmds, SettingsBtn::Scaling)
+}
+
+fn on_settings_btn_click_system(
+    In(entity): In<Entity>,
+    btns: Query<&SettingsBtn>,
+    mut settings: ResMut<Settings>,
+) {
+    let Ok(btn) = btns.get(entity) else {
+        return;
+    };
+
+    btn.apply(&mut settings);
+    settings.store();
+}
+
+fn update_settings_btns_system(
+    settings: Res<Settings>,
+    mut btns: Query<(&mut UITextButton, Ref<SettingsBtn>)>,
+) {
+    btns.iter_mut().for_each(|(mut text_btn, btn)| {
+        if !settings.is_changed() && !btn.is_added() {
+            return;
+        }
+
+        text_btn.text = btn.label(&settings).to_string();
+    });
+}
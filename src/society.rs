@@ -0,0 +1,223 @@
+use rkit::{gfx::Color, math::Vec2, prelude::*};
+
+use crate::{
+    consts::*,
+    economy::{Money, SimSet, TotalStock},
+    game::{BuildKind, Building, ResourceKind, Shutdown},
+    ledger::{Ledger, LedgerCategory},
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        load_bar::UILoadBar,
+        slider::UISlider,
+        tooltip::{TooltipContainer, TooltipNode, clear_tooltips, create_text_tooltip},
+    },
+};
+
+pub fn society_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_society_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Settle.on_tick((happiness_system, taxes_system).chain()),
+        )
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            (
+                tax_slider_system,
+                update_happiness_meter_system,
+                happiness_tooltip_system,
+            ),
+        );
+}
+
+#[derive(Resource)]
+pub struct Society {
+    pub tax_rate: f32,
+    /// From 0 to 1
+    pub happiness: f32,
+    /// Factors that move the happiness up or down
+    pub modifiers: Vec<(&'static str, f32)>,
+    /// Tax income of the last tick
+    pub tax_income: f32,
+    /// Average pollution around the houses
+    pub pollution: f32,
+}
+
+impl Default for Society {
+    fn default() -> Self {
+        Self {
+            tax_rate: DEFAULT_TAX_RATE,
+            happiness: HAPPINESS_BASE,
+            modifiers: vec![],
+            tax_income: 0.0,
+            pollution: 0.0,
+        }
+    }
+}
+
+impl Society {
+    /// Multiplier for the population growth of the houses
+    pub fn growth(&self) -> f32 {
+        0.5 + self.happiness
+    }
+
+    /// Multiplier for the output of the buildings with workers
+    pub fn productivity(&self) -> f32 {
+        0.75 + self.happiness * 0.5
+    }
+
+    fn target(&self) -> f32 {
+        let delta: f32 = self.modifiers.iter().map(|(_, v)| v).sum();
+        (HAPPINESS_BASE + delta).clamp(0.0, 1.0)
+    }
+}
+
+// - systems
+fn init_society_system(mut cmds: Commands) {
+    cmds.insert_resource(Society::default());
+}
+
+pub fn happiness_system(mut society: ResMut<Society>, stock: Res<TotalStock>) {
+    let food = if stock.get(ResourceKind::Food) > 0.0 {
+        0.1
+    } else {
+        -0.3
+    };
+
+    society.modifiers = vec![
+        ("Taxes", -society.tax_rate * TAX_UNHAPPINESS),
+        ("Food", food),
+        ("Pollution", -society.pollution * POLLUTION_UNHAPPINESS),
+    ];
+
+    let target = society.target();
+    society.happiness += (target - society.happiness) * HAPPINESS_CHANGE_RATE;
+}
+
+pub fn taxes_system(
+    buildings: Query<&BuildKind, (With<Building>, Without<Shutdown>)>,
+    stock: Res<TotalStock>,
+    mut society: ResMut<Society>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+) {
+    let has_houses = buildings
+        .iter()
+        .any(|kind| matches!(kind, BuildKind::House));
+    society.tax_income = if has_houses {
+        stock.get(ResourceKind::People) * society.tax_rate * TAX_PER_PERSON
+    } else {
+        0.0
+    };
+
+    money.0 += society.tax_income;
+    ledger.money(LedgerCategory::Taxes, society.tax_income);
+}
+
+// - ui
+#[derive(Component, Clone, Copy)]
+pub struct TaxSlider;
+
+#[derive(Component, Clone, Copy)]
+pub struct TaxText;
+
+#[derive(Component, Clone, Copy)]
+pub struct HappinessMeter;
+
+pub fn tax_slider() -> (TaxSlider, UISlider, UIStyle) {
+    (
+        TaxSlider,
+        UISlider::new(DEFAULT_TAX_RATE / MAX_TAX_RATE),
+        UIStyle::default().size(48.0, 6.0),
+    )
+}
+
+pub fn happiness_meter() -> (HappinessMeter, UILoadBar, UIPointer, UIStyle) {
+    (
+        HappinessMeter,
+        UILoadBar {
+            bg_color: PICO8_BLACK,
+            border_width: 1.0,
+            progress: HAPPINESS_BASE,
+            ..Default::default()
+        },
+        UIPointer::default(),
+        UIStyle::default().size(24.0, 6.0),
+    )
+}
+
+fn happiness_color(happiness: f32) -> Color {
+    if happiness < 0.3 {
+        PICO8_RED
+    } else if happiness < 0.6 {
+        PICO8_ORANGE
+    } else {
+        PICO8_GREEN
+    }
+}
+
+fn tax_slider_system(
+    slider: Single<&UISlider, With<TaxSlider>>,
+    text: Single<&mut UIText, With<TaxText>>,
+    mut society: ResMut<Society>,
+) {
+    let rate = slider.value * MAX_TAX_RATE;
+    if society.tax_rate != rate {
+        society.tax_rate = rate;
+    }
+
+    text.into_inner().text = format!("Tax {:.0}%", society.tax_rate * 100.0);
+}
+
+fn update_happiness_meter_system(
+    meter: Single<&mut UILoadBar, With<HappinessMeter>>,
+    society: Res<Society>,
+) {
+    let mut meter = meter.into_inner();
+    meter.progress = society.happiness;
+    meter.fill_color = happiness_color(society.happiness);
+}
+
+fn happiness_tooltip_system(
+    mut cmds: Commands,
+    meter: Single<(&UIPointer, &UINode), With<HappinessMeter>>,
+    tooltip_container: Single<Entity, With<TooltipContainer>>,
+    tooltip_nodes: Query<Entity, With<TooltipNode>>,
+    society: Res<Society>,
+) {
+    let layout = UIGameLayout;
+    let (pointer, node) = meter.into_inner();
+    if pointer.just_enter() {
+        let mut lines = society
+            .modifiers
+            .iter()
+            .map(|(name, value)| {
+                let color = if *value < 0.0 { PICO8_RED } else { PICO8_GREEN };
+                (format!("{name}: {:+.0}%", value * 100.0), color)
+            })
+            .collect::<Vec<_>>();
+
+        lines.push((
+            format!("Growth: x{:.2}", society.growth()),
+            PICO8_LIGHT_GRAY,
+        ));
+        lines.push((
+            format!("Productivity: x{:.2}", society.productivity()),
+            PICO8_LIGHT_GRAY,
+        ));
+        lines.push((
+            format!("Taxes: +${:.2}/s", society.tax_income / SIM_TICK_TIME),
+            PICO8_LIGHT_GRAY,
+        ));
+
+        let title = format!("Happiness {:.0}%", society.happiness * 100.0);
+        let pos = node.position() + node.size() * Vec2::X;
+        let tooltip = create_text_tooltip(&mut cmds, layout, &title, &lines, pos);
+        cmds.add_ui_child(layout, tooltip_container.into_inner(), tooltip);
+    } else if pointer.just_exit() {
+        clear_tooltips(&mut cmds, layout, &tooltip_nodes);
+    }
+}
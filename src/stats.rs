@@ -0,0 +1,257 @@
+use std::collections::VecDeque;
+
+use rkit::{gfx::Color, math::Vec2, prelude::*};
+use rustc_hash::FxHashMap;
+use strum::IntoEnumIterator;
+
+use crate::{
+    assets::Assets,
+    consts::*,
+    economy::{Market, Money, SimSet, TotalStock},
+    game::ResourceKind,
+    screens::AppScreen,
+    ui::{
+        UIGameLayout,
+        btns::create_text_btn,
+        chart::UIChart,
+        click::UIOnClick,
+        panel::{
+            PanelContainer, PanelNode, create_panel, panel_row, panel_text, spawn_panel_node,
+            toggle_panel,
+        },
+        tooltip::{TooltipContainer, TooltipNode, clear_tooltips, create_text_tooltip},
+    },
+};
+
+pub fn stats_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_stats_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Record.on_tick(record_stats_system),
+        )
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            (update_charts_system, resource_tooltip_system),
+        );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatKey {
+    Money,
+    Amount(ResourceKind),
+    Price(ResourceKind),
+}
+
+/// Fixed size buffer, the oldest value is dropped when it's full
+#[derive(Clone, Debug, Default)]
+pub struct Series {
+    values: VecDeque<f32>,
+}
+
+impl Series {
+    pub fn push(&mut self, value: f32) {
+        if self.values.len() >= STATS_CAPACITY {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    pub fn values(&self) -> Vec<f32> {
+        self.values.iter().copied().collect()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct StatsHistory {
+    series: FxHashMap<StatKey, Series>,
+    ticks: u32,
+}
+
+impl StatsHistory {
+    pub fn series(&self, key: StatKey) -> Option<&Series> {
+        self.series.get(&key)
+    }
+
+    fn record(&mut self, key: StatKey, value: f32) {
+        self.series.entry(key).or_default().push(value);
+    }
+}
+
+// - systems
+fn init_stats_system(mut cmds: Commands) {
+    cmds.insert_resource(StatsHistory::default());
+}
+
+fn record_stats_system(
+    mut history: ResMut<StatsHistory>,
+    stock: Res<TotalStock>,
+    market: Res<Market>,
+    money: Res<Money>,
+) {
+    history.ticks += 1;
+    if history.ticks < STATS_INTERVAL_TICKS {
+        return;
+    }
+
+    history.ticks = 0;
+    history.record(StatKey::Money, money.0);
+    ResourceKind::iter().for_each(|kind| {
+        history.record(StatKey::Amount(kind), stock.get(kind));
+        history.record(StatKey::Price(kind), market.price(kind));
+    });
+}
+
+// - ui
+/// Chart that displays the values of the stat
+#[derive(Component, Clone, Copy)]
+pub struct ChartSource(pub StatKey);
+
+fn update_charts_system(
+    mut charts: Query<(&mut UIChart, &ChartSource)>,
+    new_charts: Query<(), Added<ChartSource>>,
+    history: Res<StatsHistory>,
+) {
+    if !history.is_changed() && new_charts.is_empty() {
+        return;
+    }
+
+    charts.iter_mut().for_each(|(mut chart, source)| {
+        chart.values = history
+            .series(source.0)
+            .map(|s| s.values())
+            .unwrap_or_default();
+    });
+}
+
+#[derive(Component, Clone, Copy)]
+struct StatsPanel;
+
+pub fn create_stats_btn(cmds: &mut Commands) -> Entity {
+    create_text_btn(
+        cmds,
+        UIGameLayout,
+        "Stats",
+        48.0,
+        UIOnClick::run(toggle_stats_panel_system),
+    )
+}
+
+fn toggle_stats_panel_system(
+    In(_): In<Entity>,
+    mut cmds: Commands,
+    opened: Option<Single<Entity, With<StatsPanel>>>,
+    nodes: Query<(Entity, &PanelNode)>,
+    container: Single<Entity, With<PanelContainer>>,
+    assets: Res<Assets>,
+) {
+    let container = container.into_inner();
+    toggle_panel(&mut cmds, opened.map(|e| *e), &nodes, |cmds| {
+        let (panel, content) = create_panel(cmds, container, "Stats", StatsPanel);
+        spawn_panel_node(cmds, panel, content, panel_text("Money", PICO8_WHITE));
+        spawn_panel_node(
+            cmds,
+            panel,
+            content,
+            (
+                ChartSource(StatKey::Money),
+                UIChart::line(PICO8_YELLOW),
+                UIStyle::default().size(180.0, 40.0),
+            ),
+        );
+
+        let header = spawn_panel_node(cmds, panel, content, panel_row());
+        spawn_panel_node(cmds, panel, header, panel_text("Amount", PICO8_INDIGO));
+        spawn_panel_node(cmds, panel, header, panel_text("Price", PICO8_INDIGO));
+
+        ResourceKind::iter().for_each(|kind| {
+            let row = spawn_panel_node(cmds, panel, content, panel_row());
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                UIImage {
+                    sprite: assets.resource(kind).clone(),
+                },
+            );
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (
+                    ChartSource(StatKey::Amount(kind)),
+                    UIChart::sparkline(PICO8_GREEN),
+                    UIStyle::default().size(64.0, 10.0),
+                ),
+            );
+            spawn_panel_node(
+                cmds,
+                panel,
+                row,
+                (
+                    ChartSource(StatKey::Price(kind)),
+                    UIChart::sparkline(PICO8_ORANGE),
+                    UIStyle::default().size(64.0, 10.0),
+                ),
+            );
+        });
+    });
+}
+
+/// Shows the history of the resource on hover
+#[derive(Component, Clone, Copy)]
+pub struct ResourceInfo(ResourceKind);
+
+pub fn resource_info(kind: ResourceKind) -> (ResourceInfo, UIPointer) {
+    (ResourceInfo(kind), UIPointer::default())
+}
+
+/// Adds a sparkline with the history of the stat at the bottom of the tooltip
+pub fn add_tooltip_sparkline(cmds: &mut Commands, tooltip: Entity, key: StatKey, color: Color) {
+    let layout = UIGameLayout;
+    let chart = cmds
+        .spawn_ui_node(
+            layout,
+            (
+                TooltipNode,
+                ChartSource(key),
+                UIChart::sparkline(color),
+                UIStyle::default().size(80.0, 12.0),
+            ),
+        )
+        .entity_id();
+    cmds.add_ui_child(layout, tooltip, chart);
+}
+
+fn resource_tooltip_system(
+    mut cmds: Commands,
+    query: Query<(&UIPointer, &UINode, &ResourceInfo)>,
+    tooltip_container: Single<Entity, With<TooltipContainer>>,
+    tooltip_nodes: Query<Entity, With<TooltipNode>>,
+    stock: Res<TotalStock>,
+    market: Res<Market>,
+) {
+    let layout = UIGameLayout;
+    let tooltip_container = tooltip_container.into_inner();
+    query.iter().for_each(|(pointer, node, info)| {
+        if pointer.just_enter() {
+            let kind = info.0;
+            let lines: [(String, Color); 2] = [
+                (
+                    format!("Stored: {:.0}/{:.0}", stock.get(kind), stock.capacity(kind)),
+                    PICO8_WHITE,
+                ),
+                (format!("Price: ${:.2}", market.price(kind)), PICO8_ORANGE),
+            ];
+            let pos = node.position() + node.size() * Vec2::X;
+            let tooltip = create_text_tooltip(&mut cmds, layout, kind.name(), &lines, pos);
+            cmds.add_ui_child(layout, tooltip_container, tooltip);
+
+            add_tooltip_sparkline(&mut cmds, tooltip, StatKey::Amount(kind), PICO8_GREEN);
+        } else if pointer.just_exit() {
+            clear_tooltips(&mut cmds, layout, &tooltip_nodes);
+        }
+    });
+}
@@ -0,0 +1,89 @@
+use rkit::gfx::Color;
+
+use crate::{
+    consts::*,
+    game::{BuildKind, ResourceKind},
+    seed::SeedRng,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Terrain {
+    #[default]
+    Plain,
+    Fertile,
+    Rocky,
+    Vein(ResourceKind),
+    Water,
+}
+
+impl Terrain {
+    pub fn generate(rng: &mut SeedRng) -> Self {
+        match rng.range(0, 100) {
+            0..35 => Terrain::Plain,
+            35..55 => Terrain::Fertile,
+            55..75 => Terrain::Rocky,
+            75..90 => Terrain::Vein(Self::generate_ore(rng)),
+            _ => Terrain::Water,
+        }
+    }
+
+    fn generate_ore(rng: &mut SeedRng) -> ResourceKind {
+        match rng.range(0, 100) {
+            0..45 => ResourceKind::Copper,
+            45..75 => ResourceKind::Iron,
+            75..92 => ResourceKind::Silver,
+            _ => ResourceKind::Gold,
+        }
+    }
+
+    /// Production multiplier for a building placed on this terrain
+    pub fn multiplier(&self, kind: BuildKind) -> f32 {
+        match (self, kind) {
+            (Terrain::Fertile, BuildKind::Farm) => 2.0,
+            (Terrain::Fertile, BuildKind::Forest) => 1.5,
+            (Terrain::Rocky, BuildKind::Farm) => 0.5,
+            (Terrain::Rocky, BuildKind::Forest) => 0.5,
+            (Terrain::Rocky, BuildKind::Mine) => 1.25,
+            (Terrain::Vein(_), BuildKind::Mine) => 2.0,
+            (Terrain::Water, BuildKind::Farm) => 1.25,
+            (Terrain::Water, BuildKind::Mine) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Resource extracted by a mine placed on this terrain
+    pub fn ore(&self) -> ResourceKind {
+        match self {
+            Terrain::Vein(kind) => *kind,
+            _ => ResourceKind::Copper,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Terrain::Plain => "Plain",
+            Terrain::Fertile => "Fertile soil",
+            Terrain::Rocky => "Rocky ground",
+            Terrain::Vein(ResourceKind::Copper) => "Copper vein",
+            Terrain::Vein(ResourceKind::Iron) => "Iron vein",
+            Terrain::Vein(ResourceKind::Silver) => "Silver vein",
+            Terrain::Vein(ResourceKind::Gold) => "Gold vein",
+            Terrain::Vein(_) => "Vein",
+            Terrain::Water => "Water",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Terrain::Plain => PICO8_DARK_GRAY,
+            Terrain::Fertile => PICO8_DARK_GREEN,
+            Terrain::Rocky => PICO8_LIGHT_GRAY,
+            Terrain::Vein(ResourceKind::Copper) => PICO8_BROWN,
+            Terrain::Vein(ResourceKind::Iron) => PICO8_INDIGO,
+            Terrain::Vein(ResourceKind::Silver) => PICO8_WHITE,
+            Terrain::Vein(ResourceKind::Gold) => PICO8_YELLOW,
+            Terrain::Vein(_) => PICO8_ORANGE,
+            Terrain::Water => PICO8_BLUE,
+        }
+    }
+}
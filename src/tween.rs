@@ -0,0 +1,95 @@
+use rkit::{math::Vec2, prelude::*};
+
+use crate::components::Pos;
+
+pub fn tween_plugin(app: &mut App) {
+    app.add_systems(OnUpdate, tween_pos_system);
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicOut,
+    CubicInOut,
+}
+
+impl Easing {
+    /// Maps the progress from 0 to 1 to the eased one
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) * 0.5
+                }
+            }
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) * 0.5
+                }
+            }
+        }
+    }
+}
+
+/// Moves the `Pos` to a point over time, removed once it arrives
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PosTween {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub duration: f32,
+    pub easing: Easing,
+    elapsed: f32,
+}
+
+impl PosTween {
+    pub fn new(from: Vec2, to: Vec2, duration: f32, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        self.elapsed / self.duration
+    }
+
+    pub fn value(&self) -> Vec2 {
+        self.from.lerp(self.to, self.easing.apply(self.progress()))
+    }
+}
+
+// - systems
+fn tween_pos_system(
+    mut cmds: Commands,
+    mut query: Query<(Entity, &mut Pos, &mut PosTween)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_f32();
+    query.iter_mut().for_each(|(entity, mut pos, mut tween)| {
+        tween.elapsed += dt;
+        pos.0 = tween.value();
+        if tween.progress() >= 1.0 {
+            cmds.entity(entity).remove::<PosTween>();
+        }
+    });
+}
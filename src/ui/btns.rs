@@ -1,4 +1,3 @@
-
 use rkit::{
     draw::{Draw2D, Sprite},
     math::{Vec2, vec2},
@@ -50,3 +49,58 @@ fn render_img_btn(draw: &mut Draw2D, (btn, node): (&UIImgButton, &UINode)) {
         .stroke(2.0)
         .alpha(0.8);
 }
+
+#[derive(Component, Debug, Clone)]
+#[require(UIStyle, UIPointer, UIRender(text_btn_render_component))]
+pub struct UITextButton {
+    pub text: String,
+    pub enabled: bool,
+}
+
+fn text_btn_render_component() -> UIRender {
+    UIRender::run::<(&UITextButton, &UINode), _>(render_text_btn)
+}
+
+fn render_text_btn(draw: &mut Draw2D, (btn, node): (&UITextButton, &UINode)) {
+    let (bg_color, color) = if btn.enabled {
+        (PICO8_BLACK, PICO8_WHITE)
+    } else {
+        (PICO8_DARK_GRAY, PICO8_LIGHT_GRAY)
+    };
+
+    draw.rect(Vec2::ZERO, node.size())
+        .fill_color(bg_color)
+        .fill();
+
+    draw.rect(Vec2::ZERO, node.size())
+        .stroke_color(PICO8_LIGHT_GRAY)
+        .stroke(1.0);
+
+    draw.text(&btn.text)
+        .translate(node.size() * 0.5)
+        .origin(Vec2::splat(0.5))
+        .color(color)
+        .size(8.0)
+        .h_align_center();
+}
+
+pub fn create_text_btn<L: Component + Copy>(
+    cmds: &mut Commands,
+    layout: L,
+    text: &str,
+    width: f32,
+    b: impl Bundle,
+) -> Entity {
+    cmds.spawn_ui_node(
+        layout,
+        (
+            UITextButton {
+                text: text.to_string(),
+                enabled: true,
+            },
+            UIStyle::default().size(width, 12.0),
+            b,
+        ),
+    )
+    .entity_id()
+}
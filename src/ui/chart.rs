@@ -0,0 +1,110 @@
+use rkit::{
+    draw::Draw2D,
+    gfx::Color,
+    math::{Vec2, vec2},
+    prelude::*,
+};
+
+use crate::consts::*;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChartKind {
+    /// Line with background, border and the range values
+    #[default]
+    Line,
+    /// Just the line, to be used inline with other nodes
+    Sparkline,
+}
+
+#[derive(Component, Debug, Clone)]
+#[require(UIStyle, UIRender(chart_renderer))]
+pub struct UIChart {
+    pub kind: ChartKind,
+    pub values: Vec<f32>,
+    pub color: Color,
+    pub bg_color: Color,
+}
+
+impl Default for UIChart {
+    fn default() -> Self {
+        Self {
+            kind: ChartKind::Line,
+            values: vec![],
+            color: PICO8_GREEN,
+            bg_color: PICO8_BLACK,
+        }
+    }
+}
+
+impl UIChart {
+    pub fn sparkline(color: Color) -> Self {
+        Self {
+            kind: ChartKind::Sparkline,
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub fn line(color: Color) -> Self {
+        Self {
+            kind: ChartKind::Line,
+            color,
+            ..Default::default()
+        }
+    }
+}
+
+fn chart_renderer() -> UIRender {
+    UIRender::run::<(&UIChart, &UINode), _>(render_chart_sys)
+}
+
+fn render_chart_sys(draw: &mut Draw2D, (chart, node): (&UIChart, &UINode)) {
+    let size = node.size();
+    let is_line = chart.kind == ChartKind::Line;
+    if is_line {
+        draw.rect(Vec2::ZERO, size)
+            .fill_color(chart.bg_color)
+            .fill();
+    }
+
+    let (min, max) = chart
+        .values
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+
+    if chart.values.len() >= 2 {
+        let range = (max - min).max(f32::EPSILON);
+        let step = size.x / (chart.values.len() - 1) as f32;
+        let points = chart
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| vec2(i as f32 * step, size.y - (v - min) / range * size.y))
+            .collect::<Vec<_>>();
+
+        points.windows(2).for_each(|p| {
+            draw.line(p[0], p[1]).color(chart.color).width(1.0);
+        });
+    }
+
+    if is_line {
+        draw.rect(Vec2::ZERO, size)
+            .stroke_color(PICO8_LIGHT_GRAY)
+            .stroke(1.0);
+
+        if !chart.values.is_empty() {
+            draw.text(&format!("{max:.0}"))
+                .translate(vec2(2.0, 1.0))
+                .color(PICO8_LIGHT_GRAY)
+                .size(6.0);
+
+            draw.text(&format!("{min:.0}"))
+                .translate(vec2(2.0, size.y - 1.0))
+                .origin(vec2(0.0, 1.0))
+                .color(PICO8_LIGHT_GRAY)
+                .size(6.0);
+        }
+    }
+}
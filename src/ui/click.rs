@@ -1,9 +1,10 @@
-
 use rkit::{
     ecs::bevy_ecs::system::{BoxedSystem, SystemId},
     prelude::*,
 };
 
+use crate::input::Pointer;
+
 #[derive(Default)]
 enum SysState {
     #[default]
@@ -56,19 +57,25 @@ pub(super) fn dispatch_on_click_system(world: &mut World) {
         }
     });
 
-    let systems = world
-        .query_filtered::<(Entity, &UIOnClick, &UIPointer), Without<ClickSysNeedsRegister>>()
-        .iter(world)
-        .filter_map(|(entity, on, pointer)| {
-            if pointer.just_clicked(MouseButton::Left) {
-                if let SysState::Id(id) = on.state {
-                    return Some((entity, id));
-                }
-            }
+    let system_of = |entity: Entity, on: &UIOnClick| match on.state {
+        SysState::Id(id) => Some((entity, id)),
+        _ => None,
+    };
+
+    // taps do not move the mouse, so the node comes from the pointer hit test
+    let tapped = tapped_node(world).and_then(|entity| {
+        let on = world.get::<UIOnClick>(entity)?;
+        system_of(entity, on)
+    });
 
-            None
-        })
+    let mut query =
+        world.query_filtered::<(Entity, &UIOnClick, &UIPointer), Without<ClickSysNeedsRegister>>();
+    let mut systems = query
+        .iter(world)
+        .filter(|(_, _, pointer)| pointer.just_clicked(MouseButton::Left))
+        .filter_map(|(entity, on, ..)| system_of(entity, on))
         .collect::<Vec<_>>();
+    systems.extend(tapped);
 
     systems.into_iter().for_each(|(entity, sys)| {
         world
@@ -76,3 +83,14 @@ pub(super) fn dispatch_on_click_system(world: &mut World) {
             .or_panic("Running UIOnClick System");
     });
 }
+
+/// Topmost node under the tap of this frame, none for mouse clicks
+fn tapped_node(world: &World) -> Option<Entity> {
+    let mouse_click = world.resource::<Mouse>().just_pressed(MouseButton::Left);
+    let pointer = world.get_resource::<Pointer>()?;
+    if !pointer.click || mouse_click {
+        return None;
+    }
+
+    pointer.ui_node
+}
@@ -1,20 +1,10 @@
 use rkit::{
     draw::{HAlign, Sprite},
     gfx::Color,
-    math::{Vec2, vec2},
     prelude::*,
-    random,
 };
 
-use crate::{assets::Assets, consts::*, game::ResourceKind};
-
-use super::{
-    UIGameLayout,
-    tooltip::{ResInfo, TooltipContainer, create_btn_info_tooltip},
-};
-
-#[derive(Component, Clone, Copy)]
-pub struct CounterInfo;
+use crate::consts::*;
 
 pub fn create_img_counter<C: Component, L: Component + Copy>(
     cmds: &mut Commands,
@@ -80,32 +70,3 @@ pub fn create_img_counter<C: Component, L: Component + Copy>(
 
     container
 }
-
-pub(super) fn show_counter_info_system(
-    mut cmds: Commands,
-    query: Query<(&UIPointer, &UINode), With<CounterInfo>>,
-    tooltip_container: Single<Entity, With<TooltipContainer>>,
-    assets: Res<Assets>,
-) {
-    let layout = UIGameLayout;
-    let tooltip_container = tooltip_container.into_inner();
-    query.iter().for_each(|(pointer, node)| {
-        if pointer.just_enter() {
-            let pos = node.position() + node.size() * Vec2::X;
-            let tooltip = create_btn_info_tooltip(
-                &mut cmds,
-                layout,
-                "whatever",
-                &[ResInfo {
-                    kind: ResourceKind::Iron,
-                    amount: 0.0,
-                }],
-                assets.as_ref(),
-                (),
-                pos,
-            );
-            cmds.add_ui_child(layout, tooltip_container, tooltip);
-        } else if pointer.just_exit() {
-        }
-    });
-}
@@ -0,0 +1,121 @@
+use rkit::{
+    math::{Rect, Vec2},
+    prelude::*,
+};
+
+use crate::{
+    camera::{Cam, UICam},
+    input::Pointer,
+};
+
+/// Frame in which the node was added to the layout, the layout draws the
+/// nodes in the order they are added so the later ones are on top
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UIDrawOrder(u64);
+
+/// Topmost rect that contains the position, the nodes added later are drawn
+/// over the previous ones and the ones added together are children drawn
+/// over their parents
+pub fn topmost_at(
+    nodes: impl Iterator<Item = (Entity, Rect, UIDrawOrder)>,
+    pos: Vec2,
+) -> Option<Entity> {
+    let inside = |a: &Rect, b: &Rect| {
+        a.min().cmpge(b.min()).all() && (a.min() + a.size).cmple(b.min() + b.size).all()
+    };
+
+    nodes
+        .filter(|(_, rect, _)| rect.contains(pos))
+        .max_by(|(a_e, a, a_order), (b_e, b, b_order)| {
+            let nested = match (inside(a, b), inside(b, a)) {
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                _ => a_e.cmp(b_e),
+            };
+            a_order.cmp(b_order).then(nested)
+        })
+        .map(|(entity, ..)| entity)
+}
+
+// - systems
+pub(super) fn add_draw_order_system(
+    mut cmds: Commands,
+    added: Query<Entity, Added<UINode>>,
+    mut frame: Local<u64>,
+) {
+    *frame += 1;
+    added.iter().for_each(|entity| {
+        cmds.entity(entity).insert(UIDrawOrder(*frame));
+    });
+}
+
+/// Only the nodes that take the input or paint a background hide what is below
+pub(super) fn update_pointer_ui_system(
+    mut pointer: ResMut<Pointer>,
+    nodes: Query<(
+        Entity,
+        &UINode,
+        &UIDrawOrder,
+        Option<&UIContainer>,
+        Has<UIPointer>,
+    )>,
+    cam: Single<&Cam, With<UICam>>,
+) {
+    let solid = nodes
+        .iter()
+        .filter(|(.., container, has_pointer)| {
+            *has_pointer || container.is_some_and(|c| c.bg_color.is_some())
+        })
+        .map(|(entity, node, order, ..)| (entity, Rect::new(node.position(), node.size()), *order));
+    pointer.ui_node = topmost_at(solid, cam.mouse_pos);
+
+    // the node pressed keeps the drag until it is released
+    if pointer.pressed {
+        pointer.ui_grab = pointer.ui_node;
+    } else if !pointer.down {
+        pointer.ui_grab = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::math::vec2;
+
+    use super::*;
+
+    fn node(idx: u32, pos: Vec2, size: Vec2, frame: u64) -> (Entity, Rect, UIDrawOrder) {
+        (
+            Entity::from_raw(idx),
+            Rect::new(pos, size),
+            UIDrawOrder(frame),
+        )
+    }
+
+    #[test]
+    fn children_are_over_their_parents() {
+        // the child has a lower entity but it was added with the parent
+        let panel = node(5, Vec2::ZERO, vec2(100.0, 100.0), 1);
+        let btn = node(2, vec2(10.0, 10.0), vec2(20.0, 10.0), 1);
+        let nodes = [panel, btn];
+        assert_eq!(
+            topmost_at(nodes.into_iter(), vec2(15.0, 15.0)),
+            Some(Entity::from_raw(2))
+        );
+        assert_eq!(
+            topmost_at(nodes.into_iter(), vec2(50.0, 50.0)),
+            Some(Entity::from_raw(5))
+        );
+        assert_eq!(topmost_at(nodes.into_iter(), vec2(150.0, 50.0)), None);
+    }
+
+    #[test]
+    fn later_nodes_cover_the_smaller_ones_below() {
+        let btn = node(1, vec2(10.0, 10.0), vec2(20.0, 10.0), 1);
+        let panel = node(7, Vec2::ZERO, vec2(100.0, 100.0), 3);
+        let nodes = [btn, panel];
+        assert_eq!(
+            topmost_at(nodes.into_iter(), vec2(15.0, 15.0)),
+            Some(Entity::from_raw(7))
+        );
+    }
+}
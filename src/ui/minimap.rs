@@ -0,0 +1,84 @@
+use rkit::{
+    draw::Draw2D,
+    gfx::Color,
+    math::{Rect, Vec2},
+    prelude::*,
+};
+
+use crate::consts::*;
+
+#[derive(Component, Debug, Clone)]
+#[require(UIStyle, UIPointer, UIRender(minimap_renderer))]
+pub struct UIMinimap {
+    /// Area of the world shown
+    pub bounds: Rect,
+    pub rects: Vec<(Rect, Color)>,
+    /// Area seen by the camera
+    pub view: Rect,
+    pub bg_color: Color,
+    pub view_color: Color,
+}
+
+impl Default for UIMinimap {
+    fn default() -> Self {
+        Self {
+            bounds: Rect::new(Vec2::ZERO, Vec2::ONE),
+            rects: vec![],
+            view: Rect::new(Vec2::ZERO, Vec2::ONE),
+            bg_color: PICO8_BLACK,
+            view_color: PICO8_WHITE,
+        }
+    }
+}
+
+impl UIMinimap {
+    /// Scale and offset to fit the bounds centered on the node
+    fn transform(&self, size: Vec2) -> (f32, Vec2) {
+        let scale = (size / self.bounds.size.max(Vec2::ONE)).min_element();
+        let offset = (size - self.bounds.size * scale) * 0.5;
+        (scale, offset)
+    }
+
+    fn to_local(&self, point: Vec2, size: Vec2) -> Vec2 {
+        let (scale, offset) = self.transform(size);
+        (point - self.bounds.origin) * scale + offset
+    }
+
+    /// World point under a point relative to the node
+    pub fn to_world(&self, local: Vec2, size: Vec2) -> Vec2 {
+        let (scale, offset) = self.transform(size);
+        (local - offset) / scale + self.bounds.origin
+    }
+}
+
+fn minimap_renderer() -> UIRender {
+    UIRender::run::<(&UIMinimap, &UINode), _>(render_minimap_sys)
+}
+
+fn render_minimap_sys(draw: &mut Draw2D, (minimap, node): (&UIMinimap, &UINode)) {
+    let size = node.size();
+    draw.rect(Vec2::ZERO, size)
+        .fill_color(minimap.bg_color)
+        .alpha(0.8)
+        .fill();
+
+    let (scale, _) = minimap.transform(size);
+    minimap.rects.iter().for_each(|(rect, color)| {
+        draw.rect(minimap.to_local(rect.origin, size), rect.size * scale)
+            .fill_color(*color)
+            .fill();
+    });
+
+    // camera frame, clipped to the node
+    let min = minimap.to_local(minimap.view.origin, size).max(Vec2::ZERO);
+    let max = minimap
+        .to_local(minimap.view.origin + minimap.view.size, size)
+        .min(size);
+    draw.rect(min, (max - min).max(Vec2::ZERO))
+        .stroke_color(minimap.view_color)
+        .stroke(1.0);
+
+    draw.rect(Vec2::ZERO, size)
+        .stroke_color(PICO8_LIGHT_GRAY)
+        .stroke(1.0);
+}
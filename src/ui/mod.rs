@@ -1,12 +1,17 @@
 pub mod btns;
+pub mod chart;
 pub mod click;
 pub mod counter;
+pub mod hit;
 pub mod load_bar;
+pub mod minimap;
+pub mod panel;
+pub mod slider;
 pub mod tooltip;
 
 use rkit::prelude::*;
 
-use crate::screens::AppScreen;
+use crate::{camera::update_camera_system, screens::AppScreen};
 
 #[derive(Component, Clone, Copy)]
 pub struct UILoadLayout;
@@ -15,6 +20,12 @@ pub struct UILoadLayout;
 pub struct UIGameLayout;
 
 pub fn ui_plugin(app: &mut App) {
-    app.add_systems(OnUpdate, (click::dispatch_on_click_system,))
-        .add_screen_systems(AppScreen::Game, OnUpdate, counter::show_counter_info_system);
+    app.add_systems(
+        OnPreUpdate,
+        (hit::add_draw_order_system, hit::update_pointer_ui_system)
+            .chain()
+            .after(update_camera_system),
+    )
+    .add_systems(OnUpdate, (click::dispatch_on_click_system,))
+    .add_screen_systems(AppScreen::Game, OnUpdate, slider::update_sliders_system);
 }
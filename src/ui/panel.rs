@@ -0,0 +1,137 @@
+use rkit::{draw::HAlign, gfx::Color, prelude::*};
+
+use crate::consts::*;
+
+use super::{UIGameLayout, btns::create_text_btn, click::UIOnClick};
+
+/// Container where the panels are placed
+#[derive(Component, Clone, Copy)]
+pub struct PanelContainer;
+
+/// Node that belongs to a panel, despawned when the panel is closed
+#[derive(Component, Clone, Copy)]
+pub struct PanelNode(pub Entity);
+
+/// Spawn a node that will be despawned along with the panel
+pub fn spawn_panel_node(
+    cmds: &mut Commands,
+    panel: Entity,
+    parent: Entity,
+    bundle: impl Bundle,
+) -> Entity {
+    let layout = UIGameLayout;
+    let node = cmds
+        .spawn_ui_node(layout, (PanelNode(panel), bundle))
+        .entity_id();
+    cmds.add_ui_child(layout, parent, node);
+    node
+}
+
+pub fn panel_text(text: impl Into<String>, color: Color) -> UIText {
+    UIText {
+        text: text.into(),
+        color,
+        size: 8.0,
+        h_align: HAlign::Left,
+        ..Default::default()
+    }
+}
+
+pub fn panel_row() -> (UIContainer, UIStyle) {
+    (
+        UIContainer::default(),
+        UIStyle::default()
+            .flex_row()
+            .gap_x(6.0)
+            .justify_content_space_between()
+            .align_items_center(),
+    )
+}
+
+/// Creates a panel with a title and a close button, returns the panel
+/// and the container where the content must be added
+pub fn create_panel(
+    cmds: &mut Commands,
+    container: Entity,
+    title: &str,
+    comp: impl Bundle,
+) -> (Entity, Entity) {
+    let layout = UIGameLayout;
+    let panel = cmds
+        .spawn_ui_node(
+            layout,
+            (
+                comp,
+                UIContainer {
+                    bg_color: Some(PICO8_DARK_BLUE),
+                    border_color: Some(PICO8_LIGHT_GRAY),
+                    border_size: 1.0,
+                },
+                UIStyle::default()
+                    .flex_col()
+                    .min_width(180.0)
+                    .padding(6.0)
+                    .gap_y(4.0),
+            ),
+        )
+        .entity_id();
+
+    cmds.add_ui_child(layout, container, panel);
+
+    let header = spawn_panel_node(cmds, panel, panel, panel_row());
+    spawn_panel_node(cmds, panel, header, panel_text(title, PICO8_WHITE));
+    let close = create_text_btn(
+        cmds,
+        layout,
+        "x",
+        12.0,
+        (PanelNode(panel), UIOnClick::run(close_panel_system)),
+    );
+    cmds.add_ui_child(layout, header, close);
+
+    let content = spawn_panel_node(
+        cmds,
+        panel,
+        panel,
+        (
+            UIContainer::default(),
+            UIStyle::default().flex_col().gap_y(2.0),
+        ),
+    );
+
+    (panel, content)
+}
+
+pub fn close_panel(cmds: &mut Commands, panel: Entity, nodes: &Query<(Entity, &PanelNode)>) {
+    let layout = UIGameLayout;
+    nodes
+        .iter()
+        .filter(|(_, node)| node.0 == panel)
+        .for_each(|(e, _)| cmds.despawn_ui_node(layout, e));
+    cmds.despawn_ui_node(layout, panel);
+}
+
+fn close_panel_system(
+    In(entity): In<Entity>,
+    mut cmds: Commands,
+    nodes: Query<(Entity, &PanelNode)>,
+) {
+    let Ok((_, node)) = nodes.get(entity) else {
+        return;
+    };
+
+    close_panel(&mut cmds, node.0, &nodes);
+}
+
+/// Open the panel if it's closed or close it otherwise
+pub fn toggle_panel(
+    cmds: &mut Commands,
+    opened: Option<Entity>,
+    nodes: &Query<(Entity, &PanelNode)>,
+    open: impl FnOnce(&mut Commands),
+) {
+    match opened {
+        Some(panel) => close_panel(cmds, panel, nodes),
+        None => open(cmds),
+    }
+}
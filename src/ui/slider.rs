@@ -0,0 +1,97 @@
+use rkit::{
+    draw::Draw2D,
+    gfx::Color,
+    math::{Vec2, vec2},
+    prelude::*,
+};
+
+use crate::{
+    camera::{Cam, UICam},
+    consts::*,
+    input::Pointer,
+};
+
+#[derive(Component, Debug, Clone, Copy)]
+#[require(UIStyle, UIPointer, UIRender(slider_renderer))]
+pub struct UISlider {
+    pub bg_color: Color,
+    pub fill_color: Color,
+    pub value: f32,
+    hover: bool,
+}
+
+impl Default for UISlider {
+    fn default() -> Self {
+        Self {
+            bg_color: PICO8_BLACK,
+            fill_color: PICO8_ORANGE,
+            value: 0.0,
+            hover: false,
+        }
+    }
+}
+
+impl UISlider {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            ..Default::default()
+        }
+    }
+}
+
+fn slider_renderer() -> UIRender {
+    UIRender::run::<(&UISlider, &UINode), _>(render_slider_sys)
+}
+
+fn render_slider_sys(draw: &mut Draw2D, (slider, node): (&UISlider, &UINode)) {
+    let size = node.size();
+
+    draw.rect(Vec2::ZERO, size)
+        .fill_color(slider.bg_color)
+        .fill();
+
+    draw.rect(Vec2::ZERO, vec2(size.x * slider.value, size.y))
+        .fill_color(slider.fill_color)
+        .fill();
+
+    let stroke_color = if slider.hover {
+        PICO8_WHITE
+    } else {
+        PICO8_LIGHT_GRAY
+    };
+    draw.rect(Vec2::ZERO, size)
+        .stroke_color(stroke_color)
+        .stroke(1.0);
+
+    // knob
+    draw.rect(
+        vec2(size.x * slider.value - 1.0, -1.0),
+        vec2(2.0, size.y + 2.0),
+    )
+    .fill_color(PICO8_WHITE)
+    .fill();
+}
+
+pub(super) fn update_sliders_system(
+    mut sliders: Query<(Entity, &mut UISlider, &UIPointer, &UINode)>,
+    cam: Single<&Cam, With<UICam>>,
+    pointer: Res<Pointer>,
+) {
+    let pointer_pos = cam.mouse_pos;
+    sliders
+        .iter_mut()
+        .for_each(|(entity, mut slider, ui_pointer, node)| {
+            if ui_pointer.just_enter() {
+                slider.hover = true;
+            } else if ui_pointer.just_exit() {
+                slider.hover = false;
+            }
+
+            // the mouse or the finger drags it while it is down since the press on it
+            if pointer.ui_grab == Some(entity) {
+                let value = (pointer_pos.x - node.position().x) / node.size().x;
+                slider.value = value.clamp(0.0, 1.0);
+            }
+        });
+}
@@ -1,4 +1,4 @@
-use rkit::{draw::*, math::Vec2, prelude::*};
+use rkit::{draw::*, gfx::Color, math::Vec2, prelude::*};
 
 use crate::{
     assets::{self, Assets},
@@ -13,6 +13,18 @@ pub fn tooltip_plugin(app: &mut App) {
 #[derive(Component, Clone, Copy)]
 pub struct TooltipContainer;
 
+/// Node that belongs to a tooltip
+#[derive(Component, Clone, Copy)]
+pub struct TooltipNode;
+
+pub fn clear_tooltips<L: Component + Copy>(
+    cmds: &mut Commands,
+    layout: L,
+    nodes: &Query<Entity, With<TooltipNode>>,
+) {
+    nodes.iter().for_each(|e| cmds.despawn_ui_node(layout, e));
+}
+
 pub struct ResInfo {
     pub kind: ResourceKind,
     pub amount: f32,
@@ -31,6 +43,7 @@ pub fn create_btn_info_tooltip<L: Component + Copy>(
         .spawn_ui_node(
             layout,
             (
+                TooltipNode,
                 UIContainer {
                     bg_color: Some(PICO8_DARK_BLUE),
                     border_color: Some(PICO8_LIGHT_GRAY),
@@ -52,13 +65,16 @@ pub fn create_btn_info_tooltip<L: Component + Copy>(
     let title = cmds
         .spawn_ui_node(
             layout,
-            UIText {
-                text: name.to_string(),
-                color: PICO8_WHITE,
-                size: 8.0,
-                h_align: HAlign::Center,
-                ..Default::default()
-            },
+            (
+                TooltipNode,
+                UIText {
+                    text: name.to_string(),
+                    color: PICO8_WHITE,
+                    size: 8.0,
+                    h_align: HAlign::Center,
+                    ..Default::default()
+                },
+            ),
         )
         .entity_id();
 
@@ -69,6 +85,7 @@ pub fn create_btn_info_tooltip<L: Component + Copy>(
             .spawn_ui_node(
                 layout,
                 (
+                    TooltipNode,
                     UIContainer {
                         bg_color: Some(PICO8_BLACK),
                         border_color: Some(PICO8_LIGHT_GRAY),
@@ -90,23 +107,17 @@ pub fn create_btn_info_tooltip<L: Component + Copy>(
 
         cmds.add_ui_child(layout, container, row_container);
 
-        let img = match info.kind {
-            ResourceKind::Copper => &assets.copper,
-            ResourceKind::Iron => &assets.iron,
-            ResourceKind::Silver => &assets.silver,
-            ResourceKind::Gold => &assets.gold,
-            ResourceKind::Woord => &assets.wood,
-            ResourceKind::Food => &assets.food,
-            ResourceKind::People => &assets.people,
-            ResourceKind::Ring => &assets.ring,
-        };
+        let img = assets.resource(info.kind);
 
         let img_c = cmds
             .spawn_ui_node(
                 layout,
-                UIImage {
-                    sprite: img.clone(),
-                },
+                (
+                    TooltipNode,
+                    UIImage {
+                        sprite: img.clone(),
+                    },
+                ),
             )
             .entity_id();
 
@@ -123,18 +134,90 @@ pub fn create_btn_info_tooltip<L: Component + Copy>(
         let txt_c = cmds
             .spawn_ui_node(
                 layout,
-                (UIText {
-                    font: Some(assets.font.clone()),
-                    text: txt,
-                    color,
-                    size: 12.0,
+                (
+                    TooltipNode,
+                    UIText {
+                        font: Some(assets.font.clone()),
+                        text: txt,
+                        color,
+                        size: 12.0,
+                        h_align: HAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+            )
+            .entity_id();
+
+        cmds.add_ui_child(layout, row_container, txt_c);
+    });
+
+    container
+}
+
+/// Tooltip with a title and lines of text
+pub fn create_text_tooltip<L: Component + Copy>(
+    cmds: &mut Commands,
+    layout: L,
+    name: &str,
+    lines: &[(String, Color)],
+    pos: Vec2,
+) -> Entity {
+    let container = cmds
+        .spawn_ui_node(
+            layout,
+            (
+                TooltipNode,
+                UIContainer {
+                    bg_color: Some(PICO8_DARK_BLUE),
+                    border_color: Some(PICO8_LIGHT_GRAY),
+                    border_size: 1.0,
+                },
+                UIStyle::default()
+                    .left(pos.x)
+                    .top(pos.y)
+                    .flex_col()
+                    .padding(6.0)
+                    .gap_y(2.0),
+            ),
+        )
+        .entity_id();
+
+    let title = cmds
+        .spawn_ui_node(
+            layout,
+            (
+                TooltipNode,
+                UIText {
+                    text: name.to_string(),
+                    color: PICO8_WHITE,
+                    size: 8.0,
                     h_align: HAlign::Center,
                     ..Default::default()
-                },),
+                },
+            ),
+        )
+        .entity_id();
+
+    cmds.add_ui_child(layout, container, title);
+
+    lines.iter().for_each(|(text, color)| {
+        let line = cmds
+            .spawn_ui_node(
+                layout,
+                (
+                    TooltipNode,
+                    UIText {
+                        text: text.clone(),
+                        color: *color,
+                        size: 8.0,
+                        h_align: HAlign::Left,
+                        ..Default::default()
+                    },
+                ),
             )
             .entity_id();
 
-        cmds.add_ui_child(layout, row_container, txt_c);
+        cmds.add_ui_child(layout, container, line);
     });
 
     container
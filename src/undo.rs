@@ -0,0 +1,244 @@
+use std::collections::VecDeque;
+
+use rkit::prelude::*;
+
+use crate::{
+    build::{BuildAction, Reverted},
+    consts::*,
+    economy::{Money, SimClock},
+    game::Building,
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    screens::AppScreen,
+};
+
+pub fn undo_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_undo_system)
+        .add_screen_systems(screen, OnUpdate, undo_keys_system);
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UndoEntry {
+    pub action: BuildAction,
+    /// Money paid, refunded when undone
+    pub cost: f32,
+    /// Building affected by the action
+    pub target: Option<Entity>,
+    pub tick: u64,
+}
+
+#[derive(Resource, Default)]
+pub struct UndoStack {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+impl UndoStack {
+    /// Adds a new action, anything undone before can't be redone anymore
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.redo.clear();
+        self.push_undo(entry);
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if self.undo.len() >= UNDO_MAX {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    /// Points the entries of a building that came back as a new entity to it
+    fn retarget(&mut self, old: Entity, new: Entity) {
+        self.undo
+            .iter_mut()
+            .chain(self.redo.iter_mut())
+            .for_each(|entry| {
+                entry.action = entry.action.retarget(old, new);
+                if entry.target == Some(old) {
+                    entry.target = Some(new);
+                }
+            });
+    }
+}
+
+// - systems
+fn init_undo_system(mut cmds: Commands) {
+    cmds.insert_resource(UndoStack::default());
+}
+
+fn undo_keys_system(mut cmds: Commands, keyboard: Res<Keyboard>) {
+    let ctrl = keyboard.is_down(KeyCode::ControlLeft)
+        || keyboard.is_down(KeyCode::ControlRight)
+        || keyboard.is_down(KeyCode::SuperLeft)
+        || keyboard.is_down(KeyCode::SuperRight);
+    if !ctrl {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyZ) {
+        cmds.queue(undo);
+    } else if keyboard.just_pressed(KeyCode::KeyY) {
+        cmds.queue(redo);
+    }
+}
+
+fn undo(world: &mut World) {
+    let Some(entry) = world.resource_mut::<UndoStack>().undo.pop_back() else {
+        return;
+    };
+
+    // only recent actions whose building didn't produce anything yet
+    let tick = world.resource::<SimClock>().tick;
+    let expired = tick.saturating_sub(entry.tick) > UNDO_WINDOW_TICKS;
+    let produced = entry
+        .target
+        .and_then(|e| world.get::<Building>(e))
+        .is_some_and(|b| b.produced);
+    // undoing a demolition gives the refund back
+    if world.resource::<Money>().0 < -entry.cost {
+        world.resource_mut::<UndoStack>().undo.push_back(entry);
+        world
+            .resource_mut::<Notifications>()
+            .push("Not enough money to undo", PICO8_ORANGE, None);
+        return;
+    }
+
+    let reverted = if expired || produced {
+        Reverted::No
+    } else {
+        entry.action.revert(world, entry.target)
+    };
+
+    if reverted == Reverted::No {
+        world.resource_mut::<UndoStack>().undo.push_back(entry);
+        world
+            .resource_mut::<Notifications>()
+            .push("Too late to undo", PICO8_ORANGE, None);
+        return;
+    }
+
+    world.resource_mut::<Money>().0 += entry.cost;
+    world
+        .resource_mut::<Ledger>()
+        .money(LedgerCategory::Construction, entry.cost);
+    let mut stack = world.resource_mut::<UndoStack>();
+    stack.redo.push(entry);
+    if let (Reverted::Respawned(new), Some(old)) = (reverted, entry.target) {
+        stack.retarget(old, new);
+    }
+}
+
+fn redo(world: &mut World) {
+    let Some(entry) = world.resource_mut::<UndoStack>().redo.pop() else {
+        return;
+    };
+
+    let missing = entry
+        .action
+        .building()
+        .is_some_and(|building| world.get::<Building>(building).is_none());
+    if missing {
+        world.resource_mut::<Notifications>().push(
+            "Can't redo, the building is gone",
+            PICO8_ORANGE,
+            None,
+        );
+        return;
+    }
+
+    if world.resource::<Money>().0 < entry.cost {
+        world.resource_mut::<UndoStack>().redo.push(entry);
+        world
+            .resource_mut::<Notifications>()
+            .push("Not enough money to redo", PICO8_ORANGE, None);
+        return;
+    }
+
+    world.resource_mut::<Money>().0 -= entry.cost;
+    world
+        .resource_mut::<Ledger>()
+        .money(LedgerCategory::Construction, -entry.cost);
+    let target = entry.action.apply(world);
+    let tick = world.resource::<SimClock>().tick;
+    let mut stack = world.resource_mut::<UndoStack>();
+    stack.push_undo(UndoEntry {
+        target,
+        tick,
+        ..entry
+    });
+
+    // a rebuilt building is a new entity, the next redos must follow it
+    let moved = entry.target.zip(target).filter(|(old, new)| old != new);
+    if let Some((old, new)) = moved {
+        stack.retarget(old, new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkit::math::UVec2;
+
+    use super::*;
+    use crate::game::BuildKind;
+
+    fn entry(action: BuildAction, target: Option<Entity>) -> UndoEntry {
+        UndoEntry {
+            action,
+            cost: 10.0,
+            target,
+            tick: 0,
+        }
+    }
+
+    #[test]
+    fn rebuilt_building_keeps_its_redo_chain() {
+        let land = Entity::from_raw(0);
+        let old = Entity::from_raw(1);
+        let new = Entity::from_raw(2);
+        let build = BuildAction::Build {
+            land,
+            pos: UVec2::ZERO,
+            kind: BuildKind::Farm,
+        };
+
+        // build and upgrade undone, the build is redone as a new entity
+        let mut stack = UndoStack::default();
+        stack
+            .redo
+            .push(entry(BuildAction::Upgrade { building: old }, Some(old)));
+        stack.push_undo(entry(build, Some(new)));
+        stack.retarget(old, new);
+
+        let upgrade = stack.redo.pop().unwrap();
+        assert_eq!(upgrade.target, Some(new));
+        assert_eq!(upgrade.action.building(), Some(new));
+        assert_eq!(stack.undo.back().unwrap().target, Some(new));
+    }
+
+    #[test]
+    fn new_action_clears_the_redo() {
+        let building = Entity::from_raw(1);
+        let mut stack = UndoStack::default();
+        stack
+            .redo
+            .push(entry(BuildAction::Upgrade { building }, Some(building)));
+        stack.push(entry(BuildAction::Upgrade { building }, Some(building)));
+        assert!(stack.redo.is_empty());
+        assert_eq!(stack.undo.len(), 1);
+    }
+
+    #[test]
+    fn undo_keeps_the_last_entries() {
+        let building = Entity::from_raw(1);
+        let mut stack = UndoStack::default();
+        (0..UNDO_MAX + 5).for_each(|tick| {
+            stack.push(UndoEntry {
+                tick: tick as u64,
+                ..entry(BuildAction::Upgrade { building }, None)
+            });
+        });
+        assert_eq!(stack.undo.len(), UNDO_MAX);
+        assert_eq!(stack.undo.front().unwrap().tick, 5);
+    }
+}
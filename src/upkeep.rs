@@ -0,0 +1,154 @@
+use rkit::prelude::*;
+
+use crate::{
+    consts::*,
+    economy::{Money, SimSet},
+    game::{BuildKind, Building, Shutdown},
+    ledger::{Ledger, LedgerCategory},
+    notify::Notifications,
+    screens::AppScreen,
+};
+
+pub fn upkeep_plugin(app: &mut App) {
+    let screen = AppScreen::Game;
+    app.add_systems(OnEnter(screen), init_upkeep_system)
+        .add_screen_systems(
+            screen,
+            OnUpdate,
+            SimSet::Settle.on_tick((upkeep_system, shutdown_system, bankruptcy_system).chain()),
+        );
+}
+
+/// Ticks that the player has been in debt
+#[derive(Resource, Default)]
+pub struct Debt {
+    pub ticks: u32,
+}
+
+impl Debt {
+    /// Ticks left before going bankrupt
+    pub fn ticks_left(&self) -> u32 {
+        BANKRUPTCY_TICKS.saturating_sub(self.ticks)
+    }
+}
+
+impl BuildKind {
+    /// Base money spent each tick to keep the building running
+    pub fn upkeep(&self) -> f32 {
+        match self {
+            BuildKind::Farm => 0.1,
+            BuildKind::House => 0.1,
+            BuildKind::Forest => 0.05,
+            BuildKind::Factory => 1.0,
+            BuildKind::Shop => 0.3,
+            BuildKind::Mine => 0.2,
+            BuildKind::Warehouse => 0.4,
+            BuildKind::Depot => 0.3,
+        }
+    }
+
+    /// Lower values are shut down first when the player is in debt
+    pub fn shutdown_priority(&self) -> u32 {
+        match self {
+            BuildKind::Factory => 0,
+            BuildKind::Warehouse => 1,
+            BuildKind::Depot => 1,
+            BuildKind::House => 2,
+            BuildKind::Forest => 3,
+            BuildKind::Farm => 4,
+            BuildKind::Mine => 5,
+            BuildKind::Shop => 6,
+        }
+    }
+}
+
+impl Building {
+    #[inline]
+    pub fn upkeep(&self, kind: BuildKind) -> f32 {
+        kind.upkeep() * self.level as f32
+    }
+}
+
+fn init_upkeep_system(mut cmds: Commands) {
+    cmds.insert_resource(Debt::default());
+}
+
+pub fn upkeep_system(
+    buildings: Query<(&BuildKind, &Building), Without<Shutdown>>,
+    mut money: ResMut<Money>,
+    mut ledger: ResMut<Ledger>,
+) {
+    let upkeep: f32 = buildings
+        .iter()
+        .map(|(kind, building)| building.upkeep(*kind))
+        .sum();
+
+    money.0 -= upkeep;
+    ledger.money(LedgerCategory::Upkeep, -upkeep);
+}
+
+fn shutdown_system(
+    mut cmds: Commands,
+    running: Query<(Entity, &BuildKind, &Building), Without<Shutdown>>,
+    stopped: Query<(Entity, &BuildKind, &Building), With<Shutdown>>,
+    money: Res<Money>,
+    mut notifications: ResMut<Notifications>,
+) {
+    // in debt, shut down one building per tick following the priority
+    if money.0 < 0.0 {
+        let next = running
+            .iter()
+            .min_by_key(|(_, kind, b)| (kind.shutdown_priority(), u32::MAX - b.level));
+        if let Some((entity, kind, _)) = next {
+            cmds.entity(entity).insert(Shutdown);
+            notifications.push(
+                format!("{} shut down, no money", kind.name()),
+                PICO8_ORANGE,
+                Some(entity),
+            );
+        }
+        return;
+    }
+
+    // restart in reverse order once there is money to keep them running for a while
+    let next = stopped
+        .iter()
+        .max_by_key(|(_, kind, b)| (kind.shutdown_priority(), b.level));
+    if let Some((entity, kind, building)) = next {
+        let reserve = building.upkeep(*kind) * RESTART_UPKEEP_RESERVE;
+        if money.0 >= reserve {
+            cmds.entity(entity).remove::<Shutdown>();
+            notifications.push(
+                format!("{} running again", kind.name()),
+                PICO8_GREEN,
+                Some(entity),
+            );
+        }
+    }
+}
+
+fn bankruptcy_system(
+    mut cmds: Commands,
+    mut debt: ResMut<Debt>,
+    money: Res<Money>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if money.0 >= 0.0 {
+        debt.ticks = 0;
+        return;
+    }
+
+    if debt.ticks == 0 {
+        let secs = BANKRUPTCY_TICKS as f32 * SIM_TICK_TIME;
+        notifications.push(
+            format!("In debt! {secs:.0}s to bankruptcy"),
+            PICO8_RED,
+            None,
+        );
+    }
+
+    debt.ticks += 1;
+    if debt.ticks >= BANKRUPTCY_TICKS {
+        cmds.queue(ChangeScreen(AppScreen::GameOver));
+    }
+}
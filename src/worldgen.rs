@@ -0,0 +1,129 @@
+use rkit::math::{IVec2, UVec2, Vec2, ivec2};
+
+use crate::{consts::*, game::Land, seed::SeedRng};
+
+// Salt to keep the world layout independent of the terrain generation
+const WORLD_SALT: u64 = 0x3017_1D;
+
+pub struct LandPlot {
+    pub pos: Vec2,
+    pub land: Land,
+}
+
+/// Generate the land plots around the starting one. The player owns the land
+/// in the center of the grid, the rest are available to buy.
+pub fn generate_world(seed: u64) -> Vec<LandPlot> {
+    let mut rng = SeedRng::fork(seed, WORLD_SALT);
+    let half = (WORLD_GRID / 2).as_ivec2();
+
+    let mut plots = vec![];
+    let UVec2 { x: cols, y: rows } = WORLD_GRID;
+    for y in 0..rows {
+        for x in 0..cols {
+            let cell = ivec2(x as _, y as _) - half;
+            let is_start = cell == IVec2::ZERO;
+            if !is_start && !rng.chance(WORLD_LAND_CHANCE) {
+                continue;
+            }
+
+            // each land uses its own generator so adding plots does not
+            // change the terrain of the others
+            let mut land_rng = SeedRng::fork(seed, (y * cols + x) as u64 + 1);
+            let mut land = Land::new(&mut land_rng);
+            land.owned = is_start;
+            land.price = if is_start {
+                0.0
+            } else {
+                land_price(cell, &mut rng)
+            };
+
+            plots.push(LandPlot {
+                pos: cell_pos(cell),
+                land,
+            });
+        }
+    }
+
+    plots
+}
+
+pub fn cell_pos(cell: IVec2) -> Vec2 {
+    RESOLUTION * 0.5 + cell.as_vec2() * WORLD_CELL_SIZE
+}
+
+fn land_price(cell: IVec2, rng: &mut SeedRng) -> f32 {
+    let distance = (cell.x.abs() + cell.y.abs()) as f32;
+    let variance = 0.75 + rng.next_f32() * 0.5;
+    (LAND_BASE_PRICE * distance * variance).round()
+}
+
+/// Inverse of `cell_pos`
+pub fn cell_at(pos: Vec2) -> IVec2 {
+    ((pos - RESOLUTION * 0.5) / WORLD_CELL_SIZE)
+        .round()
+        .as_ivec2()
+}
+
+/// Name of the land at the position, used by the route editor
+pub fn land_label(pos: Vec2) -> String {
+    let cell = cell_at(pos);
+    format!("{},{}", cell.x, cell.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::ResourceKind, terrain::Terrain};
+
+    fn code(terrain: Terrain) -> char {
+        match terrain {
+            Terrain::Plain => 'p',
+            Terrain::Fertile => 'f',
+            Terrain::Rocky => 'r',
+            Terrain::Vein(ResourceKind::Copper) => 'c',
+            Terrain::Vein(ResourceKind::Iron) => 'i',
+            Terrain::Vein(ResourceKind::Silver) => 's',
+            Terrain::Vein(ResourceKind::Gold) => 'g',
+            Terrain::Vein(_) => 'v',
+            Terrain::Water => 'w',
+        }
+    }
+
+    fn describe(plot: &LandPlot) -> String {
+        let cell = cell_at(plot.pos);
+        let terrain: String = plot.land.terrain.iter().copied().map(code).collect();
+        format!("{},{} {:.0} {}", cell.x, cell.y, plot.land.price, terrain)
+    }
+
+    #[test]
+    fn fixed_seed_gives_the_stored_world() {
+        let world: Vec<_> = generate_world(0x1D57_EC0D_DEE9)
+            .iter()
+            .map(describe)
+            .collect();
+        let expected = [
+            "-2,-1 398 prrfrpwfi",
+            "-1,-1 364 irrwffrfp",
+            "0,-1 118 iwppwpprp",
+            "2,-1 486 pirrrrppr",
+            "-2,0 364 fcppfrwwr",
+            "-1,0 153 ppcppppip",
+            "0,0 0 ppfcfwpff",
+            "2,0 266 rppffrfpp",
+            "-2,1 457 prpprpffc",
+            "-1,1 255 ppippppfr",
+            "0,1 187 icfprppcr",
+            "2,1 438 prrpwpisf",
+        ];
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn only_the_center_plot_is_owned() {
+        let world = generate_world(42);
+        let owned: Vec<_> = world.iter().filter(|p| p.land.owned).collect();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(cell_at(owned[0].pos), IVec2::ZERO);
+        assert_eq!(owned[0].land.price, 0.0);
+    }
+}